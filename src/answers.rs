@@ -0,0 +1,210 @@
+//! Scripted answers for the interactive wizards.
+//!
+//! Every prompt has a stable id such as `export.name` or `import.target_dir`.
+//! With `--answers <file>` the answers are taken from a TOML file keyed by
+//! those ids (`[export]` / `name = "Button"`), and with `--record <file>` the
+//! answers given interactively are written to that file for later replay.
+//!
+//! A scripted answer is used once; asking the same prompt again falls back to
+//! the terminal. Without a terminal the prompt's default is used, and prompts
+//! without a default fail.
+
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use inquire::error::InquireResult;
+use inquire::{Confirm, InquireError, MultiSelect, Select, Text};
+use toml::{Table, Value};
+
+#[derive(Default)]
+struct Session {
+	replay: Option<Table>,
+	record: Option<(PathBuf, Table)>,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+fn with_session<R>(f: impl FnOnce(&mut Session) -> R) -> R {
+	let mut guard = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+	f(guard.get_or_insert_with(Session::default))
+}
+
+/// Answers prompts from the given TOML file instead of asking the user.
+pub fn replay_from(path: &Path) -> Result<()> {
+	let content = std::fs::read_to_string(path)
+		.with_context(|| format!("Failed to read answers file {}", path.display()))?;
+	let answers: Table = toml::from_str(&content)
+		.with_context(|| format!("Failed to parse answers file {}", path.display()))?;
+	with_session(|s| s.replay = Some(answers));
+	Ok(())
+}
+
+/// Records every answer given during this session into the given TOML file.
+pub fn record_to(path: &Path) -> Result<()> {
+	with_session(|s| s.record = Some((path.to_path_buf(), Table::new())));
+	Ok(())
+}
+
+/// Asks a free-text question.
+pub fn text(id: &str, prompt: Text) -> InquireResult<String> {
+	let answer = match take_scripted(id, prompt.default.is_some())? {
+		Some(Value::String(s)) => s,
+		Some(other) => return Err(invalid_answer(id, "a string", &other)),
+		None if is_replaying() && !interactive() => prompt.default.unwrap_or_default().to_string(),
+		None => prompt.prompt()?,
+	};
+	record(id, Value::String(answer.clone()));
+	Ok(answer)
+}
+
+/// Asks the user to pick one option. A scripted answer must match the
+/// displayed text of one of the options.
+pub fn select<T: Display>(id: &str, prompt: Select<T>) -> InquireResult<T> {
+	let answer = match take_scripted(id, false)? {
+		Some(Value::String(s)) => {
+			let index = prompt.options.iter()
+				.position(|o| o.to_string() == s)
+				.ok_or_else(|| custom_error(format!("Answer `{}` for `{}` is not one of the options", s, id)))?;
+			prompt.options.into_iter().nth(index).expect("index is in range")
+		}
+		Some(other) => return Err(invalid_answer(id, "a string", &other)),
+		None => prompt.prompt()?,
+	};
+	record(id, Value::String(answer.to_string()));
+	Ok(answer)
+}
+
+/// Asks the user to pick any number of options.
+pub fn multi_select<T: Display>(id: &str, prompt: MultiSelect<T>) -> InquireResult<Vec<T>> {
+	let answer = match take_scripted(id, false)? {
+		Some(Value::Array(items)) => {
+			let mut wanted = Vec::new();
+			for item in items {
+				match item {
+					Value::String(s) => wanted.push(s),
+					other => return Err(invalid_answer(id, "an array of strings", &other)),
+				}
+			}
+			if let Some(unknown) = wanted.iter().find(|w| !prompt.options.iter().any(|o| &&o.to_string() == w)) {
+				return Err(custom_error(format!("Answer `{}` for `{}` is not one of the options", unknown, id)));
+			}
+			prompt.options.into_iter()
+				.filter(|o| wanted.contains(&o.to_string()))
+				.collect()
+		}
+		Some(other) => return Err(invalid_answer(id, "an array of strings", &other)),
+		None => prompt.prompt()?,
+	};
+	record(id, Value::Array(answer.iter().map(|o| Value::String(o.to_string())).collect()));
+	Ok(answer)
+}
+
+/// Asks a yes/no question.
+pub fn confirm(id: &str, prompt: Confirm) -> InquireResult<bool> {
+	let answer = match take_scripted(id, prompt.default.is_some())? {
+		Some(Value::Boolean(b)) => b,
+		Some(other) => return Err(invalid_answer(id, "a boolean", &other)),
+		None if is_replaying() && !interactive() => prompt.default.unwrap_or_default(),
+		None => prompt.prompt()?,
+	};
+	record(id, Value::Boolean(answer));
+	Ok(answer)
+}
+
+/// Removes and returns the scripted answer for `id`. Fails when answers are
+/// being replayed, `id` has none, the prompt has no default and there is no
+/// terminal to ask instead.
+fn take_scripted(id: &str, has_default: bool) -> InquireResult<Option<Value>> {
+	with_session(|s| {
+		let Some(answers) = s.replay.as_mut() else {
+			return Ok(None);
+		};
+		match take_path(answers, id) {
+			Some(value) => Ok(Some(value)),
+			None if has_default || interactive() => Ok(None),
+			None => Err(custom_error(format!("No scripted answer for prompt `{}`", id))),
+		}
+	})
+}
+
+fn is_replaying() -> bool {
+	with_session(|s| s.replay.is_some())
+}
+
+fn interactive() -> bool {
+	std::io::stdin().is_terminal()
+}
+
+fn record(id: &str, value: Value) {
+	let result = with_session(|s| {
+		let Some((path, answers)) = s.record.as_mut() else {
+			return Ok(());
+		};
+		insert_path(answers, id, value);
+		let content = toml::to_string_pretty(answers)
+			.context("Failed to serialize recorded answers")?;
+		std::fs::write(&*path, content)
+			.with_context(|| format!("Failed to write answers to {}", path.display()))
+	});
+	if let Err(e) = result {
+		eprintln!("⚠️  Warning: {:#}", e);
+	}
+}
+
+fn take_path(table: &mut Table, id: &str) -> Option<Value> {
+	match id.split_once('.') {
+		Some((head, rest)) => match table.get_mut(head)? {
+			Value::Table(inner) => take_path(inner, rest),
+			_ => None,
+		},
+		None => table.remove(id),
+	}
+}
+
+fn insert_path(table: &mut Table, id: &str, value: Value) {
+	match id.split_once('.') {
+		Some((head, rest)) => {
+			let entry = table.entry(head.to_string()).or_insert_with(|| Value::Table(Table::new()));
+			if !entry.is_table() {
+				*entry = Value::Table(Table::new());
+			}
+			if let Value::Table(inner) = entry {
+				insert_path(inner, rest, value);
+			}
+		}
+		None => {
+			table.insert(id.to_string(), value);
+		}
+	}
+}
+
+fn custom_error(message: String) -> InquireError {
+	InquireError::Custom(message.into())
+}
+
+fn invalid_answer(id: &str, expected: &str, got: &Value) -> InquireError {
+	custom_error(format!("Answer for `{}` must be {}, got `{}`", id, expected, got))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_nested_ids_round_trip() {
+		let mut answers = Table::new();
+		insert_path(&mut answers, "export.name", Value::String("Button".into()));
+		insert_path(&mut answers, "export.overwrite", Value::String("No".into()));
+
+		let content = toml::to_string_pretty(&answers).unwrap();
+		let mut parsed: Table = toml::from_str(&content).unwrap();
+		assert!(content.contains("[export]"));
+
+		assert_eq!(take_path(&mut parsed, "export.name"), Some(Value::String("Button".into())));
+		assert_eq!(take_path(&mut parsed, "export.name"), None, "answers are consumed");
+		assert_eq!(take_path(&mut parsed, "import.name"), None);
+	}
+}
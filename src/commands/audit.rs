@@ -0,0 +1,516 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use inquire::Confirm;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::answers;
+use crate::commands::export::ComponentMetadata;
+use crate::config::{get_config, Risk};
+use crate::library::{self, LibraryComponent};
+use crate::taxonomy::Taxonomy;
+
+/// An audit finding reviewed and accepted for a component, recorded in its
+/// `component.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AcceptedRisk {
+    pub rule: String,
+    /// File the finding is in, relative to the component directory
+    pub file: String,
+    /// The excerpt of the accepted line, so that new occurrences of the rule
+    /// in the same file are still reported. Entries without one match nothing.
+    #[serde(default)]
+    pub excerpt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditFinding {
+    pub path: PathBuf,
+    /// `path` relative to the component directory, as recorded when accepted
+    pub file: String,
+    pub line: usize,
+    pub rule: &'static str,
+    pub risk: Risk,
+    pub message: &'static str,
+    /// The offending line, shortened
+    pub excerpt: String,
+}
+
+impl AuditFinding {
+    pub fn is_accepted(&self, accepted: &[AcceptedRisk]) -> bool {
+        accepted.iter().any(|a| a.rule == self.rule && a.file == self.file && a.excerpt == self.excerpt)
+    }
+}
+
+/// A pattern matched against each line of a file.
+struct LineRule {
+    id: &'static str,
+    risk: Risk,
+    pattern: &'static str,
+    /// Extensions the rule applies to, every scanned file when empty
+    extensions: &'static [&'static str],
+    message: &'static str,
+}
+
+const LINE_RULES: &[LineRule] = &[
+    LineRule {
+        id: "v-html",
+        risk: Risk::High,
+        pattern: "\\bv-html\\s*=",
+        extensions: &["vue"],
+        message: "v-html renders raw HTML, unsafe unless the content is sanitized",
+    },
+    LineRule {
+        id: "dangerously-set-inner-html",
+        risk: Risk::High,
+        pattern: "\\bdangerouslySetInnerHTML\\b",
+        extensions: &["jsx", "tsx", "js", "ts"],
+        message: "dangerouslySetInnerHTML renders raw HTML, unsafe unless the content is sanitized",
+    },
+    LineRule {
+        id: "svelte-html",
+        risk: Risk::High,
+        pattern: "\\{@html\\b",
+        extensions: &["svelte"],
+        message: "{@html} renders raw HTML, unsafe unless the content is sanitized",
+    },
+    LineRule {
+        id: "eval",
+        risk: Risk::High,
+        pattern: "\\beval\\s*\\(",
+        extensions: &[],
+        message: "eval runs arbitrary code",
+    },
+    LineRule {
+        id: "new-function",
+        risk: Risk::High,
+        pattern: "\\bnew\\s+Function\\s*\\(",
+        extensions: &[],
+        message: "new Function runs arbitrary code",
+    },
+    LineRule {
+        id: "inner-html",
+        risk: Risk::Medium,
+        pattern: "\\.(innerHTML|outerHTML)\\s*\\+?=([^=]|$)|\\binsertAdjacentHTML\\s*\\(",
+        extensions: &[],
+        message: "assigning HTML strings bypasses the framework's escaping",
+    },
+];
+
+/// Extensions whose markup is checked tag by tag.
+const MARKUP_EXTENSIONS: &[&str] = &["html", "vue", "svelte", "jsx", "tsx", "php"];
+
+/// Scans the source files of a component for risky patterns, in file and
+/// line order. Files with an extension `taxonomy` does not know are skipped.
+pub fn scan_component(component: &LibraryComponent, taxonomy: &Taxonomy) -> Vec<AuditFinding> {
+    let patterns: Vec<(&LineRule, Regex)> = LINE_RULES.iter()
+        .map(|rule| (rule, Regex::new(rule.pattern).expect("built-in audit patterns are valid")))
+        .collect();
+    let base = if component.path.is_dir() { component.path.as_path() } else { component.path.parent().unwrap_or(Path::new("")) };
+
+    let mut findings = Vec::new();
+    for path in &component.files {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        if !taxonomy.supports_extension(&ext) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let file = path.strip_prefix(base).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        let lines: Vec<&str> = content.lines().collect();
+        let mut report = |line: usize, rule: &'static str, risk: Risk, message: &'static str| {
+            findings.push(AuditFinding {
+                path: path.clone(),
+                file: file.clone(),
+                line,
+                rule,
+                risk,
+                message,
+                excerpt: excerpt(lines.get(line - 1).copied().unwrap_or_default()),
+            });
+        };
+
+        for (rule, pattern) in &patterns {
+            if !rule.extensions.is_empty() && !rule.extensions.contains(&ext.as_str()) {
+                continue;
+            }
+            for (i, line) in lines.iter().enumerate() {
+                if pattern.is_match(line) {
+                    report(i + 1, rule.id, rule.risk, rule.message);
+                }
+            }
+        }
+
+        if MARKUP_EXTENSIONS.contains(&ext.as_str()) {
+            for (offset, tag) in tags(&content) {
+                for (rule, risk, message, at) in check_tag(tag) {
+                    report(line_of(&content, offset + at), rule, risk, message);
+                }
+            }
+        }
+    }
+    findings.sort_by(|a, b| (&a.path, a.line, a.rule).cmp(&(&b.path, b.line, b.rule)));
+    findings
+}
+
+/// The start offset and text of every opening tag in `content`, from `<` up
+/// to the `>` closing it outside quotes and `{...}` expressions.
+fn tags(content: &str) -> Vec<(usize, &str)> {
+    let bytes = content.as_bytes();
+    let mut tags = Vec::new();
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        if bytes[i] != b'<' || !bytes[i + 1].is_ascii_alphabetic() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let (mut quote, mut depth) = (None, 0usize);
+        i += 1;
+        while i < bytes.len() {
+            match (quote, bytes[i]) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, b'"' | b'\'' | b'`') => quote = Some(bytes[i]),
+                (None, b'{') => depth += 1,
+                (None, b'}') => depth = depth.saturating_sub(1),
+                (None, b'>') if depth == 0 => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        tags.push((start, &content[start..i.min(content.len())]));
+    }
+    tags
+}
+
+/// The attributes of a tag as (offset in the tag, lowercased name, value).
+/// Values in `{...}` are returned with their braces.
+fn attributes(tag: &str) -> Vec<(usize, String, Option<&str>)> {
+    let bytes = tag.as_bytes();
+    let mut attributes = Vec::new();
+    // Skip `<` and the tag name
+    let mut i = 1;
+    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'/' {
+        i += 1;
+    }
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            break;
+        }
+        let start = i;
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() && !matches!(bytes[i], b'=' | b'/') {
+            i += 1;
+        }
+        let name = tag[start..i].to_lowercase();
+        let mut j = i;
+        while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        let mut value = None;
+        if j < bytes.len() && bytes[j] == b'=' {
+            j += 1;
+            while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                j += 1;
+            }
+            let value_start = j;
+            match bytes.get(j) {
+                Some(&q @ (b'"' | b'\'')) => {
+                    j += 1;
+                    while j < bytes.len() && bytes[j] != q {
+                        j += 1;
+                    }
+                    value = Some(&tag[value_start..(j + 1).min(tag.len())]);
+                    j += 1;
+                }
+                Some(b'{') => {
+                    let mut depth = 0;
+                    while j < bytes.len() {
+                        match bytes[j] {
+                            b'{' => depth += 1,
+                            b'}' => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                        j += 1;
+                    }
+                    value = Some(&tag[value_start..(j + 1).min(tag.len())]);
+                    j += 1;
+                }
+                _ => {
+                    while j < bytes.len() && !bytes[j].is_ascii_whitespace() {
+                        j += 1;
+                    }
+                    value = Some(&tag[value_start..j]);
+                }
+            }
+            i = j;
+        }
+        if name.is_empty() {
+            i += 1;
+            continue;
+        }
+        attributes.push((start, name, value));
+    }
+    attributes
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// Findings for one tag as (rule, risk, message, offset in the tag).
+fn check_tag(tag: &str) -> Vec<(&'static str, Risk, &'static str, usize)> {
+    let name = tag[1..].split(|c: char| c.is_whitespace() || c == '/' || c == '>').next().unwrap_or_default().to_lowercase();
+    let attributes = attributes(tag);
+    let mut findings = Vec::new();
+
+    for (at, attribute, value) in &attributes {
+        let is_handler = attribute.len() > 2 && attribute.starts_with("on") && attribute[2..].chars().all(|c| c.is_ascii_alphabetic());
+        if is_handler && value.is_some_and(|v| v.starts_with('"') || v.starts_with('\'')) {
+            findings.push(("inline-handler", Risk::Medium, "inline event handler strings run as code", *at));
+        }
+    }
+
+    let value_of = |wanted: &str| attributes.iter().find(|(_, name, _)| name == wanted).map(|(at, _, value)| (*at, value.map(unquote)));
+    if let Some((at, Some("_blank"))) = value_of("target") {
+        // A bound rel can't be checked, so it is trusted
+        let protected = attributes.iter().any(|(_, name, value)| match name.as_str() {
+            ":rel" | "v-bind:rel" => true,
+            "rel" => value.is_some_and(|v| v.contains("noopener") || v.contains("noreferrer") || v.starts_with('{')),
+            _ => false,
+        });
+        if !protected {
+            findings.push(("target-blank", Risk::Low, "target=\"_blank\" without rel=\"noopener\" exposes window.opener", at));
+        }
+    }
+
+    if matches!(name.as_str(), "script" | "iframe")
+        && let Some((at, Some(src))) = value_of("src")
+        && (src.starts_with("http://") || src.starts_with("https://") || src.starts_with("//"))
+    {
+        if name == "script" {
+            findings.push(("external-script", Risk::High, "loads a script from another origin", at));
+        } else {
+            findings.push(("external-iframe", Risk::Medium, "embeds a page from another origin", at));
+        }
+    }
+    findings
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+fn excerpt(line: &str) -> String {
+    let line = line.trim();
+    match line.char_indices().nth(80) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// The risk padded to the width of `medium`, colored by level.
+fn risk_label(risk: Risk) -> String {
+    let label = risk.to_string();
+    let padding = " ".repeat(6 - label.len());
+    let label = match risk {
+        Risk::High => label.red().bold(),
+        Risk::Medium => label.yellow().bold(),
+        Risk::Low => label.blue().bold(),
+    };
+    format!("{}{}", label, padding)
+}
+
+pub fn print_findings(findings: &[AuditFinding]) {
+    for finding in findings {
+        println!(
+            "{} {}:{} {} [{}]",
+            risk_label(finding.risk),
+            finding.path.display(),
+            finding.line,
+            finding.message,
+            finding.rule
+        );
+        println!("       {}", finding.excerpt.dimmed());
+    }
+}
+
+/// The findings of `component` that have not been accepted in its metadata.
+fn open_findings(component: &LibraryComponent, taxonomy: &Taxonomy) -> (Vec<AuditFinding>, usize) {
+    let accepted = component.metadata().map(|m| m.accepted_risks).unwrap_or_default();
+    let (accepted, open): (Vec<AuditFinding>, Vec<AuditFinding>) =
+        scan_component(component, taxonomy).into_iter().partition(|f| f.is_accepted(&accepted));
+    (open, accepted.len())
+}
+
+/// Shows the unaccepted findings of the components about to be imported, and
+/// asks for confirmation when any reaches `threshold`. Returns whether to go
+/// ahead.
+pub fn review_import(components: &[&LibraryComponent], taxonomy: &Taxonomy, threshold: Risk) -> Result<bool> {
+    let findings: Vec<AuditFinding> = components.iter().flat_map(|c| open_findings(c, taxonomy).0).collect();
+    if findings.is_empty() {
+        return Ok(true);
+    }
+    println!("Security review of the imported components:");
+    print_findings(&findings);
+    let risky = findings.iter().filter(|f| f.risk >= threshold).count();
+    if risky == 0 {
+        return Ok(true);
+    }
+    answers::confirm(
+        "import.audit",
+        Confirm::new(&format!("Import despite {} finding(s) at or above {} risk?", risky, threshold)).with_default(false),
+    )
+    .context("Failed to read confirmation")
+}
+
+/// Scans one component, or the whole library, for risky patterns. Fails when
+/// unaccepted findings reach the configured threshold. With `accept`, the
+/// current findings of the component are listed and, once confirmed,
+/// recorded as accepted in its `component.toml` instead.
+pub fn audit_components(component: Option<&str>, accept: bool, reason: Option<&str>, yes: bool) -> Result<()> {
+    let config = get_config().map_err(anyhow::Error::msg)?;
+    let taxonomy = Taxonomy::load(&config.taxonomy)?;
+    let components = library::scan(Path::new(&config.components_dir));
+    let targets: Vec<&LibraryComponent> = match component {
+        Some(name) => vec![library::find(&components, name)?],
+        None => components.iter().collect(),
+    };
+
+    if accept {
+        let (Some(_), [target]) = (component, targets.as_slice()) else {
+            bail!("Name the component whose findings to accept, e.g. `audit Button --accept`");
+        };
+        let (open, _) = open_findings(target, &taxonomy);
+        if open.is_empty() {
+            println!("{} No findings to accept in {}", "✓".green().bold(), target.id());
+            return Ok(());
+        }
+        print_findings(&open);
+        println!();
+        if !yes {
+            let confirmed = answers::confirm(
+                "audit.accept",
+                Confirm::new(&format!("Accept these {} finding(s) in {}?", open.len(), target.id())).with_default(false),
+            )
+            .context("Failed to read confirmation, pass --yes to accept without asking")?;
+            if !confirmed {
+                println!("Nothing accepted");
+                return Ok(());
+            }
+        }
+        accept_findings(target, &open, reason)?;
+        println!("{} Accepted {} finding(s) in {}", "✓".green().bold(), open.len(), target.id());
+        return Ok(());
+    }
+
+    let mut findings = Vec::new();
+    let mut accepted = 0;
+    for target in &targets {
+        let (open, count) = open_findings(target, &taxonomy);
+        findings.extend(open);
+        accepted += count;
+    }
+    if findings.is_empty() {
+        println!("{} No findings in {} component(s) ({} accepted)", "✓".green().bold(), targets.len(), accepted);
+        return Ok(());
+    }
+    print_findings(&findings);
+    println!();
+    println!("{} finding(s), {} accepted", findings.len(), accepted);
+    let risky = findings.iter().filter(|f| f.risk >= config.audit.threshold).count();
+    if risky > 0 {
+        bail!(
+            "{} finding(s) at or above {} risk, review them or record them with `audit <component> --accept`",
+            risky,
+            config.audit.threshold
+        );
+    }
+    Ok(())
+}
+
+fn accept_findings(component: &LibraryComponent, findings: &[AuditFinding], reason: Option<&str>) -> Result<()> {
+    let metadata_path = component.metadata_path();
+    let content = fs::read_to_string(&metadata_path)
+        .with_context(|| format!("{} has no component.toml to record accepted findings in", component.id()))?;
+    let mut metadata: ComponentMetadata = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", metadata_path.display()))?;
+    for finding in findings {
+        if !finding.is_accepted(&metadata.accepted_risks) {
+            metadata.accepted_risks.push(AcceptedRisk {
+                rule: finding.rule.to_string(),
+                file: finding.file.clone(),
+                excerpt: finding.excerpt.clone(),
+                reason: reason.map(str::to_string),
+            });
+        }
+    }
+    let toml_string = toml::to_string_pretty(&metadata).context("Failed to serialize metadata")?;
+    fs::write(&metadata_path, toml_string).with_context(|| format!("Failed to write {}", metadata_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(file: &str, content: &str) -> Vec<(usize, &'static str, Risk)> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("vue").join("none").join("Widget");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(file), content).unwrap();
+        let component = library::find(&library::scan(temp_dir.path()), "Widget").unwrap().clone();
+        scan_component(&component, &Taxonomy::builtin()).iter().map(|f| (f.line, f.rule, f.risk)).collect()
+    }
+
+    #[test]
+    fn test_scan_component() {
+        let vue = r#"<template>
+  <div v-html="body" />
+  <a href="https://example.com" target="_blank">safe?</a>
+  <a
+    href="/docs"
+    target="_blank"
+    rel="noopener noreferrer">docs</a>
+  <button onclick="doIt()" @click="ok">Go</button>
+  <iframe src="https://maps.example.com/embed"></iframe>
+  <script src="//cdn.example.com/lib.js"></script>
+</template>
+<script setup>
+const once = 'fine'
+el.innerHTML = html
+if (el.innerHTML === '') {}
+const run = new Function('return 1')
+</script>
+"#;
+        assert_eq!(
+            rules("Widget.vue", vue),
+            [
+                (2, "v-html", Risk::High),
+                (3, "target-blank", Risk::Low),
+                (8, "inline-handler", Risk::Medium),
+                (9, "external-iframe", Risk::Medium),
+                (10, "external-script", Risk::High),
+                (14, "inner-html", Risk::Medium),
+                (16, "new-function", Risk::High),
+            ]
+        );
+
+        let jsx = "export const Html = ({ html }) => (\n  <div onClick={() => a > b} dangerouslySetInnerHTML={{ __html: html }} />\n)\nconst x = evaluate(1)\n";
+        assert_eq!(rules("Widget.jsx", jsx), [(2, "dangerously-set-inner-html", Risk::High)]);
+        assert_eq!(rules("Widget.svelte", "{@html post.body}\n<a target=\"_blank\" rel={rel}>x</a>\n"), [(1, "svelte-html", Risk::High)]);
+    }
+}
@@ -1,27 +1,193 @@
 use std::collections::{BTreeMap, BTreeSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
+use anyhow::{Context, Result};
+
+use crate::ecosystem::Ecosystem;
+use crate::library::LibraryComponent;
+use crate::semver::Range;
+use crate::source::import_specifiers;
+use crate::package_manager::{InstallCommand, PackageManager, Workspace};
+use crate::utils::{find_upwards, normalize_path};
+
+/// Extensions of files that are components in their own right; relative
+/// imports of these are internal dependencies rather than bundled files
+const COMPONENT_EXTENSIONS: &[&str] = &["vue", "svelte", "jsx", "tsx"];
+
+/// Extensions tried, in order, when resolving an extensionless import
+const RESOLVE_EXTENSIONS: &[&str] = &["vue", "svelte", "tsx", "ts", "jsx", "js", "mjs", "json"];
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(from = "StoredDependency")]
 pub enum DependencyType {
+    /// Another library component, by name and optionally the versions it
+    /// accepts, e.g. `Icon` or `Icon@^1.2.0`
+    Internal(String),
+    /// An npm package and how the component depends on it
+    Package(PackageDependency),
+    /// Packages of other ecosystems, as `name@spec` in the ecosystem's own
+    /// version syntax, e.g. `django@>=4.2,<5`
+    Pip(String),
+    Composer(String),
+    Gem(String),
+    Cargo(String),
+    Go(String),
+    Pub(String),
+}
+
+/// A dependency as written in `component.toml`. `Npm = "name@range"`
+/// predates dependency kinds and is read as a runtime `Package`.
+#[derive(Deserialize)]
+enum StoredDependency {
     Npm(String),
-    Internal(String),  // Path to internal component
+    Internal(String),
+    Package(PackageDependency),
+    Pip(String),
+    Composer(String),
+    Gem(String),
+    Cargo(String),
+    Go(String),
+    Pub(String),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ComponentDependencies {
-    pub dependencies: BTreeSet<DependencyType>,
+impl From<StoredDependency> for DependencyType {
+    fn from(stored: StoredDependency) -> Self {
+        match stored {
+            StoredDependency::Npm(requirement) => Self::npm(&requirement),
+            StoredDependency::Internal(reference) => Self::Internal(reference),
+            StoredDependency::Package(package) => Self::Package(package),
+            StoredDependency::Pip(requirement) => Self::Pip(requirement),
+            StoredDependency::Composer(requirement) => Self::Composer(requirement),
+            StoredDependency::Gem(requirement) => Self::Gem(requirement),
+            StoredDependency::Cargo(requirement) => Self::Cargo(requirement),
+            StoredDependency::Go(requirement) => Self::Go(requirement),
+            StoredDependency::Pub(requirement) => Self::Pub(requirement),
+        }
+    }
+}
+
+/// How a component depends on a package.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    /// Installed into `dependencies`
+    #[default]
+    Runtime,
+    /// Must be provided by the project, checked but never installed
+    Peer,
+    /// Installed into `devDependencies`
+    Dev,
+    /// Only needed for some features, installed when the user asks for it
+    Optional,
+}
+
+impl DependencyKind {
+    fn is_runtime(&self) -> bool {
+        *self == Self::Runtime
+    }
+}
+
+impl std::fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Runtime => "runtime",
+            Self::Peer => "peer",
+            Self::Dev => "dev",
+            Self::Optional => "optional",
+        };
+        write!(f, "{}", name)
+    }
 }
 
-impl Default for ComponentDependencies {
-    fn default() -> Self {
-        Self {
-            dependencies: BTreeSet::new(),
+/// `{ Package = { name = "@vueuse/core", version = "^10.0.0", kind = "optional", reason = "auto-resize" } }`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PackageDependency {
+    pub name: String,
+    #[serde(default = "any_version")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "DependencyKind::is_runtime")]
+    pub kind: DependencyKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+fn any_version() -> String {
+    "*".to_string()
+}
+
+impl DependencyType {
+    /// A runtime npm dependency from a `name@range` requirement.
+    pub fn npm(requirement: &str) -> Self {
+        let (name, range) = split_npm(requirement);
+        Self::Package(PackageDependency {
+            name: name.to_string(),
+            version: range.to_string(),
+            kind: DependencyKind::Runtime,
+            reason: None,
+        })
+    }
+
+    /// The npm requirement (`name@range`) and its kind, for npm packages.
+    pub fn npm_requirement(&self) -> Option<(String, DependencyKind)> {
+        match self {
+            Self::Package(package) => Some((format!("{}@{}", package.name, package.version), package.kind)),
+            _ => None,
+        }
+    }
+
+    /// The component an internal dependency refers to.
+    pub fn internal_reference(&self) -> Option<&str> {
+        match self {
+            Self::Internal(reference) => Some(reference),
+            _ => None,
+        }
+    }
+
+    /// The ecosystem and `name@spec` requirement of a non-npm package.
+    pub fn ecosystem_requirement(&self) -> Option<(Ecosystem, &str)> {
+        match self {
+            Self::Pip(requirement) => Some((Ecosystem::Pip, requirement)),
+            Self::Composer(requirement) => Some((Ecosystem::Composer, requirement)),
+            Self::Gem(requirement) => Some((Ecosystem::Gem, requirement)),
+            Self::Cargo(requirement) => Some((Ecosystem::Cargo, requirement)),
+            Self::Go(requirement) => Some((Ecosystem::Go, requirement)),
+            Self::Pub(requirement) => Some((Ecosystem::Pub, requirement)),
+            _ => None,
+        }
+    }
+
+    pub fn from_ecosystem(ecosystem: Ecosystem, requirement: String) -> Self {
+        match ecosystem {
+            Ecosystem::Pip => Self::Pip(requirement),
+            Ecosystem::Composer => Self::Composer(requirement),
+            Ecosystem::Gem => Self::Gem(requirement),
+            Ecosystem::Cargo => Self::Cargo(requirement),
+            Ecosystem::Go => Self::Go(requirement),
+            Ecosystem::Pub => Self::Pub(requirement),
+        }
+    }
+
+    /// The same dependency with its range replaced by the one in `requirement`.
+    fn with_requirement(&self, requirement: String) -> Self {
+        match self {
+            Self::Package(package) => Self::Package(PackageDependency {
+                version: split_npm(&requirement).1.to_string(),
+                ..package.clone()
+            }),
+            other => other.clone(),
         }
     }
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ComponentDependencies {
+    pub dependencies: BTreeSet<DependencyType>,
+    /// Files the component imports that are exported along with it, relative
+    /// to the component file
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub bundled: BTreeSet<String>,
+}
+
 impl ComponentDependencies {
     pub fn new() -> Self {
         Self::default()
@@ -32,69 +198,294 @@ impl ComponentDependencies {
         self.dependencies.insert(dep);
     }
 
-    /// Detect dependencies from component files
-    pub fn detect_from_component(&self, _component_path: &Path) -> Result<()> {
-        // TODO: Implement actual dependency detection
-        // 1. Parse component files for imports
-        // 2. Detect npm packages
-        // 3. Detect internal component references
+    /// Detect dependencies from a component file by parsing its imports.
+    ///
+    /// Bare specifiers become npm dependencies, with the range the source
+    /// project's `package.json` declares. Python, PHP, Ruby, Rust, Go and Dart
+    /// files become dependencies of their ecosystem instead. Imports of
+    /// components that are in `library` become internal dependencies, and
+    /// relative imports of any other file inside the component's directory are
+    /// bundled (and scanned in turn). Imports that cannot be exported are
+    /// reported as warnings.
+    pub fn detect_from_component(&mut self, component_path: &Path, library: &[LibraryComponent]) -> Result<()> {
+        for notice in self.collect_from_component(component_path, library)? {
+            println!("⚠️  {}", notice);
+        }
         Ok(())
     }
 
-    /// Check for conflicts with other dependencies
+    /// Like `detect_from_component`, but returns the imports that cannot be
+    /// exported instead of printing them.
+    pub fn collect_from_component(&mut self, component_path: &Path, library: &[LibraryComponent]) -> Result<Vec<String>> {
+        let mut notices = Vec::new();
+        let versions = project_versions(component_path);
+        let base_dir = component_path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+        let mut queue = vec![component_path.to_path_buf()];
+        let mut scanned = BTreeSet::new();
+        while let Some(file) = queue.pop() {
+            if !scanned.insert(normalize_path(&file)) {
+                continue;
+            }
+            let content = match std::fs::read_to_string(&file) {
+                Ok(content) => content,
+                Err(e) if file == component_path => {
+                    return Err(e).with_context(|| format!("Failed to read {}", file.display()));
+                }
+                // Bundled assets such as images have no imports
+                Err(_) => continue,
+            };
+            let ext = file.extension().and_then(|e| e.to_str()).unwrap_or_default();
+
+            if let Some(ecosystem) = Ecosystem::for_extension(ext) {
+                for requirement in ecosystem.detect(&file, &content) {
+                    self.add_dependency(DependencyType::from_ecosystem(ecosystem, requirement));
+                }
+                continue;
+            }
+
+            for specifier in import_specifiers(ext, &content) {
+                if specifier.starts_with('.') {
+                    let Some(resolved) = resolve_relative(&file, &specifier) else {
+                        continue;
+                    };
+                    if normalize_path(&resolved) == normalize_path(component_path) {
+                        continue;
+                    }
+                    if let Some(reference) = library_reference(&resolved, library) {
+                        self.add_dependency(DependencyType::Internal(reference));
+                    } else if let Ok(relative) = normalize_path(&resolved).strip_prefix(normalize_path(&base_dir)) {
+                        self.bundled.insert(relative.to_string_lossy().replace('\\', "/"));
+                        queue.push(resolved);
+                    } else {
+                        notices.push(format!(
+                            "{} imports {}, which is outside the component directory and will not be exported",
+                            file.display(),
+                            specifier
+                        ));
+                    }
+                } else if is_alias(&specifier) {
+                    // Project aliases such as `@/components/Icon.vue` can only
+                    // be mapped when they point at a library component
+                    match library_reference(Path::new(&specifier), library) {
+                        Some(reference) => self.add_dependency(DependencyType::Internal(reference)),
+                        None => notices.push(format!(
+                            "{} imports {}, which is not in the library and will not be exported",
+                            file.display(),
+                            specifier
+                        )),
+                    }
+                } else if !specifier.starts_with("node:") {
+                    let name = package_name(&specifier);
+                    let range = versions.get(name).map(String::as_str).unwrap_or("*");
+                    self.add_dependency(DependencyType::npm(&format!("{}@{}", name, range)));
+                }
+            }
+        }
+        Ok(notices)
+    }
+
+    /// Adds an npm dependency unless the package is already required, using
+    /// `range` only when the project's `package.json` does not declare one.
+    pub fn add_npm_default(&mut self, name: &str, range: &str, versions: &BTreeMap<String, String>) {
+        let already_required = self.dependencies.iter().any(|d| match d {
+            DependencyType::Package(package) => package.name == name,
+            _ => false,
+        });
+        if !already_required {
+            let range = versions.get(name).map(String::as_str).unwrap_or(range);
+            self.add_dependency(DependencyType::npm(&format!("{}@{}", name, range)));
+        }
+    }
+
+    /// Check for conflicts with other dependencies. Only npm requirements
+    /// whose ranges no single version can satisfy are reported.
     pub fn check_conflicts(&self, other: &Self) -> Vec<(DependencyType, DependencyType)> {
         let mut conflicts = Vec::new();
-        
-        // Check for version conflicts in npm packages
-        let self_npm: BTreeMap<_, _> = self.dependencies.iter()
-            .filter_map(|d| match d {
-                DependencyType::Npm(pkg) => Some((pkg.split('@').next().unwrap_or(""), pkg)),
-                _ => None
-            })
-            .collect();
+        for ours in &self.dependencies {
+            for theirs in &other.dependencies {
+                if let (Some((a, _)), Some((b, _))) = (ours.npm_requirement(), theirs.npm_requirement())
+                    && split_npm(&a).0 == split_npm(&b).0
+                    && intersect_requirements(&a, &b).is_none()
+                {
+                    conflicts.push((ours.clone(), theirs.clone()));
+                }
+            }
+        }
+        conflicts
+    }
 
+    /// Adds the dependencies of `other`. When both require the same npm
+    /// package with the same kind the narrowest range satisfying both is
+    /// kept; requirements that cannot be satisfied together are returned and
+    /// the existing one is kept.
+    pub fn merge(&mut self, other: &Self) -> Vec<(DependencyType, DependencyType)> {
+        let mut conflicts = Vec::new();
         for dep in &other.dependencies {
-            if let DependencyType::Npm(other_pkg) = dep {
-                if let Some(pkg_name) = other_pkg.split('@').next() {
-                    if let Some(self_pkg) = self_npm.get(&pkg_name) {
-                        if self_pkg != &other_pkg {
-                            conflicts.push((
-                                DependencyType::Npm((*self_pkg).to_string()),
-                                dep.clone()
-                            ));
+            let Some((requirement, kind)) = dep.npm_requirement() else {
+                self.add_dependency(dep.clone());
+                continue;
+            };
+            let name = split_npm(&requirement).0;
+            let existing = self.dependencies.iter()
+                .find(|d| d.npm_requirement().is_some_and(|(r, k)| k == kind && split_npm(&r).0 == name))
+                .cloned();
+            match existing {
+                None => self.add_dependency(dep.clone()),
+                Some(current) => {
+                    let (current_requirement, _) = current.npm_requirement().expect("npm dependencies have a requirement");
+                    match intersect_requirements(&current_requirement, &requirement) {
+                        Some(narrowest) => {
+                            self.dependencies.remove(&current);
+                            self.add_dependency(current.with_requirement(narrowest));
                         }
+                        None => conflicts.push((current, dep.clone())),
                     }
                 }
             }
         }
-
         conflicts
     }
 
-    /// Generate installation commands for missing dependencies
-    pub fn generate_install_commands(&self, base_path: &Path) -> Vec<String> {
-        let mut commands = Vec::new();
-        
-        // Group npm packages by version requirements
-        let mut npm_deps = Vec::new();
-        
-        for _dep in &self.dependencies {
-            match _dep {
-                DependencyType::Npm(pkg) => npm_deps.push(pkg.clone()),
-                DependencyType::Internal(path) => {
-                    let _full_path = base_path.join(path);
-                    // TODO: Handle internal component linking
-                    commands.push(format!("# Internal component: {}", path));
-                }
+    /// The commands installing the dependencies of other ecosystems, one
+    /// ecosystem after the other.
+    pub fn ecosystem_install_commands(&self) -> Vec<InstallCommand> {
+        let mut requirements: BTreeMap<Ecosystem, Vec<String>> = BTreeMap::new();
+        for (ecosystem, requirement) in self.dependencies.iter().filter_map(DependencyType::ecosystem_requirement) {
+            requirements.entry(ecosystem).or_default().push(requirement.to_string());
+        }
+        requirements.iter()
+            .flat_map(|(ecosystem, requirements)| ecosystem.install_commands(requirements))
+            .collect()
+    }
+
+    /// The commands installing the npm dependencies with `manager`, with dev
+    /// dependencies and the packages named in `dev` added as dev
+    /// dependencies. Peer dependencies are never installed; optional ones are
+    /// installed like the others, so callers leave out those not wanted.
+    pub fn install_commands(
+        &self,
+        manager: PackageManager,
+        workspace: Option<&Workspace>,
+        dev: &BTreeSet<String>,
+    ) -> Vec<InstallCommand> {
+        let mut packages = Vec::new();
+        let mut dev_packages = Vec::new();
+        for (requirement, kind) in self.dependencies.iter().filter_map(DependencyType::npm_requirement) {
+            match kind {
+                DependencyKind::Peer => {}
+                DependencyKind::Dev => dev_packages.push(requirement),
+                _ if dev.contains(split_npm(&requirement).0) => dev_packages.push(requirement),
+                _ => packages.push(requirement),
             }
         }
-        
-        if !npm_deps.is_empty() {
-            commands.insert(0, format!("npm install --save {}", npm_deps.join(" ")));
+
+        [(packages, false), (dev_packages, true)].into_iter()
+            .filter(|(packages, _)| !packages.is_empty())
+            .map(|(packages, is_dev)| manager.add_command(&packages, is_dev, workspace))
+            .collect()
+    }
+}
+
+/// The dependency ranges declared by the nearest `package.json` above `path`.
+pub fn project_versions(path: &Path) -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::new();
+    let Some(manifest) = find_package_json(path) else {
+        return versions;
+    };
+    let Some(package) = std::fs::read_to_string(&manifest).ok().and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) else {
+        return versions;
+    };
+    // Earlier sections take precedence
+    for section in ["dependencies", "peerDependencies", "devDependencies", "optionalDependencies"] {
+        for (name, range) in package.get(section).and_then(serde_json::Value::as_object).into_iter().flatten() {
+            if let Some(range) = range.as_str() {
+                versions.entry(name.clone()).or_insert_with(|| range.to_string());
+            }
         }
-        
-        commands
     }
+    versions
+}
+
+/// The nearest `package.json` in `path` or one of its ancestors.
+pub fn find_package_json(path: &Path) -> Option<PathBuf> {
+    find_upwards(path, "package.json")
+}
+
+/// Splits an internal reference such as `Icon@^1.2.0` into the component
+/// and the range of its versions that are accepted, `*` when none is given.
+pub fn split_internal(reference: &str) -> (&str, &str) {
+    match reference.split_once('@') {
+        Some((name, range)) => (name, range),
+        None => (reference, "*"),
+    }
+}
+
+/// Splits an npm requirement such as `@vueuse/core@^10.0.0` into the package
+/// name and its range, which is `*` when none is given.
+pub fn split_npm(requirement: &str) -> (&str, &str) {
+    match requirement.get(1..).and_then(|rest| rest.find('@')) {
+        Some(i) => (&requirement[..i + 1], &requirement[i + 2..]),
+        None => (requirement, "*"),
+    }
+}
+
+/// The narrowest requirement satisfying two requirements on the same
+/// package, e.g. `react@^18.2.0` for `react@^18.0.0` and `react@^18.2.0`.
+/// Ranges that are not semver (tags, URLs) only combine with themselves or
+/// with `*`.
+pub fn intersect_requirements(a: &str, b: &str) -> Option<String> {
+    let (name, range_a) = split_npm(a);
+    let range_b = split_npm(b).1;
+    let narrowest = match (Range::parse(range_a), Range::parse(range_b)) {
+        (Ok(x), Ok(y)) => x.intersect(&y)?.to_string(),
+        _ if range_a == range_b || range_b == "*" => range_a.to_string(),
+        _ if range_a == "*" => range_b.to_string(),
+        _ => return None,
+    };
+    Some(format!("{}@{}", name, narrowest))
+}
+
+/// The npm package an import specifier belongs to: `lodash/debounce` is
+/// `lodash`, `@vue/runtime-core/dist` is `@vue/runtime-core`.
+pub fn package_name(specifier: &str) -> &str {
+    let mut parts = specifier.splitn(3, '/');
+    match (parts.next(), parts.next()) {
+        (Some(scope), Some(name)) if scope.starts_with('@') => &specifier[..scope.len() + 1 + name.len()],
+        (Some(first), _) => first,
+        _ => specifier,
+    }
+}
+
+/// Project-specific import aliases (`@/`, `~/`, `#imports`, `$lib/`).
+fn is_alias(specifier: &str) -> bool {
+    specifier.starts_with("@/") || specifier.starts_with('~') || specifier.starts_with('#') || specifier.starts_with('$')
+}
+
+/// The name of the library component a component file is a copy of, if any.
+fn library_reference(path: &Path, library: &[LibraryComponent]) -> Option<String> {
+    let is_component_file = path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| COMPONENT_EXTENSIONS.contains(&ext));
+    let name = file_stem(path);
+    (is_component_file && library.iter().any(|c| c.name == name)).then_some(name)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string()
+}
+
+/// Resolves a relative import the way a bundler would: the exact file, then
+/// with a known extension, then an `index` file in the directory.
+fn resolve_relative(importer: &Path, specifier: &str) -> Option<PathBuf> {
+    let base = importer.parent().unwrap_or(Path::new("")).join(specifier);
+    if base.is_file() {
+        return Some(base);
+    }
+    RESOLVE_EXTENSIONS.iter()
+        .map(|ext| PathBuf::from(format!("{}.{}", base.display(), ext)))
+        .chain(RESOLVE_EXTENSIONS.iter().map(|ext| base.join(format!("index.{}", ext))))
+        .find(|candidate| candidate.is_file())
 }
 
 #[cfg(test)]
@@ -104,24 +495,155 @@ mod tests {
     #[test]
     fn test_dependency_conflicts() {
         let mut deps1 = ComponentDependencies::new();
-        deps1.add_dependency(DependencyType::Npm("react@^18.0.0".to_string()));
+        deps1.add_dependency(DependencyType::npm("react@^18.0.0"));
         
         let mut deps2 = ComponentDependencies::new();
-        deps2.add_dependency(DependencyType::Npm("react@^17.0.0".to_string()));
+        deps2.add_dependency(DependencyType::npm("react@^17.0.0"));
         
         let conflicts = deps1.check_conflicts(&deps2);
         assert!(!conflicts.is_empty(), "Should detect version conflict");
     }
+
+    #[test]
+    fn test_compatible_ranges_merge() {
+        let mut deps1 = ComponentDependencies::new();
+        deps1.add_dependency(DependencyType::npm("react@^18.0.0"));
+        deps1.add_dependency(DependencyType::npm("@vue/runtime-core@3"));
+
+        let mut deps2 = ComponentDependencies::new();
+        deps2.add_dependency(DependencyType::npm("react@^18.2.0"));
+        deps2.add_dependency(DependencyType::npm("@vue/runtime-core@~3.4.0"));
+        deps2.add_dependency(DependencyType::npm("clsx@^2.0.0"));
+        assert!(deps1.check_conflicts(&deps2).is_empty(), "Overlapping ranges are compatible");
+
+        let conflicts = deps1.merge(&deps2);
+        assert!(conflicts.is_empty());
+        let merged: Vec<&DependencyType> = deps1.dependencies.iter().collect();
+        assert_eq!(merged, [
+            &DependencyType::npm("@vue/runtime-core@~3.4.0"),
+            &DependencyType::npm("clsx@^2.0.0"),
+            &DependencyType::npm("react@^18.2.0"),
+        ]);
+
+        let mut deps3 = ComponentDependencies::new();
+        deps3.add_dependency(DependencyType::npm("react@^17.0.0"));
+        assert_eq!(deps1.merge(&deps3).len(), 1);
+        assert!(deps1.dependencies.contains(&DependencyType::npm("react@^18.2.0")));
+    }
+
+    #[test]
+    fn test_dependency_kinds() {
+        let deps: ComponentDependencies = toml::from_str(r#"
+            dependencies = [
+                { Npm = "clsx@^2.0.0" },
+                { Package = { name = "vue", version = "^3.3.0", kind = "peer" } },
+                { Package = { name = "@testing-library/vue", version = "^8.0.0", kind = "dev" } },
+                { Package = { name = "@vueuse/core", kind = "optional", reason = "for the auto-resize feature" } },
+            ]
+        "#)
+        .unwrap();
+        let optional = deps.dependencies.iter()
+            .find_map(|d| match d {
+                DependencyType::Package(p) if p.kind == DependencyKind::Optional => Some(p),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(optional.version, "*");
+        assert_eq!(optional.reason.as_deref(), Some("for the auto-resize feature"));
+
+        // The legacy `Npm` form is read as a runtime package and written back as one
+        assert!(deps.dependencies.contains(&DependencyType::npm("clsx@^2.0.0")));
+        let written = toml::to_string(&deps).unwrap();
+        assert!(!written.contains("Npm"), "{}", written);
+        assert_eq!(toml::from_str::<ComponentDependencies>(&written).unwrap().dependencies, deps.dependencies);
+
+        let commands: Vec<String> = deps.install_commands(PackageManager::Npm, None, &BTreeSet::new())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(commands, [
+            "npm install --save '@vueuse/core@*' clsx@^2.0.0",
+            "npm install --save-dev @testing-library/vue@^8.0.0",
+        ]);
+
+        let mut narrower = ComponentDependencies::new();
+        narrower.add_dependency(DependencyType::Package(PackageDependency {
+            name: "vue".to_string(),
+            version: "^3.4.0".to_string(),
+            kind: DependencyKind::Peer,
+            reason: None,
+        }));
+        let mut merged = deps;
+        assert!(merged.merge(&narrower).is_empty());
+        assert!(merged.dependencies.iter().any(|d| matches!(d, DependencyType::Package(p) if p.name == "vue" && p.version == "^3.4.0")));
+    }
+
+    #[test]
+    fn test_split_npm() {
+        assert_eq!(split_npm("@vue/runtime-core@3"), ("@vue/runtime-core", "3"));
+        assert_eq!(split_npm("react@^18.0.0"), ("react", "^18.0.0"));
+        assert_eq!(split_npm("@scope/pkg"), ("@scope/pkg", "*"));
+    }
     
     #[test]
-    fn test_generate_install_commands() {
+    fn test_detect_from_component() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path();
+        let source_dir = project.join("src").join("components");
+        std::fs::create_dir_all(source_dir.join("helpers")).unwrap();
+        std::fs::write(
+            project.join("package.json"),
+            r#"{ "dependencies": { "vue": "^3.4.0", "@vueuse/core": "^10.1.0" }, "devDependencies": { "date-fns": "^3.0.0" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            source_dir.join("DataTable.vue"),
+            r#"<script setup>
+import { ref } from 'vue'
+import { useResizeObserver } from '@vueuse/core/index'
+import Pagination from './Pagination.vue'
+import { formatRow } from './helpers/format'
+import './DataTable.css'
+</script>"#,
+        )
+        .unwrap();
+        std::fs::write(source_dir.join("helpers").join("format.ts"), "import { format } from 'date-fns';\nimport { chunk } from 'lodash-es';").unwrap();
+        std::fs::write(source_dir.join("DataTable.css"), ".data-table {}").unwrap();
+        std::fs::write(source_dir.join("Pagination.vue"), "<template />").unwrap();
+
+        // Pagination is not in the library yet, so it is exported along
         let mut deps = ComponentDependencies::new();
-        deps.add_dependency(DependencyType::Npm("react@^18.0.0".to_string()));
-        deps.add_dependency(DependencyType::Npm("react-dom@^18.0.0".to_string()));
-        
-        let commands = deps.generate_install_commands(Path::new("."));
-        assert!(commands[0].contains("npm install"));
-        assert!(commands[0].contains("react@^18.0.0"));
-        assert!(commands[0].contains("react-dom@^18.0.0"));
+        deps.detect_from_component(&source_dir.join("DataTable.vue"), &[]).unwrap();
+        assert!(!deps.dependencies.iter().any(|d| matches!(d, DependencyType::Internal(_))));
+        assert!(deps.bundled.contains("Pagination.vue"));
+
+        let library_dir = project.join("library").join("vue").join("none").join("Pagination");
+        std::fs::create_dir_all(&library_dir).unwrap();
+        std::fs::write(library_dir.join("Pagination.vue"), "<template />").unwrap();
+        let library = crate::library::scan(&project.join("library"));
+        let mut deps = ComponentDependencies::new();
+        deps.detect_from_component(&source_dir.join("DataTable.vue"), &library).unwrap();
+
+        let expected: BTreeSet<DependencyType> = [
+            DependencyType::npm("vue@^3.4.0"),
+            DependencyType::npm("@vueuse/core@^10.1.0"),
+            DependencyType::npm("date-fns@^3.0.0"),
+            DependencyType::npm("lodash-es@*"),
+            DependencyType::Internal("Pagination".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(deps.dependencies, expected);
+        assert_eq!(
+            deps.bundled.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["DataTable.css", "helpers/format.ts"]
+        );
+    }
+
+    #[test]
+    fn test_package_name() {
+        assert_eq!(package_name("lodash/debounce"), "lodash");
+        assert_eq!(package_name("@vue/runtime-core/dist/x"), "@vue/runtime-core");
+        assert_eq!(package_name("react"), "react");
     }
 }
@@ -0,0 +1,196 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::commands::dependencies::split_internal;
+use crate::config::{get_config, ProjectConfig};
+use crate::library::{self, LibraryComponent};
+use crate::lockfile::{Lockfile, LOCKFILE_NAME};
+use crate::semver::{parse_version, Range};
+
+/// A library component that depends on the one looked up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependent {
+    /// `framework/style/name` of the dependent component
+    pub id: String,
+    /// The versions it accepts, for direct dependents
+    pub range: Option<String>,
+    /// The component it depends through, for transitive dependents
+    pub via: Option<String>,
+}
+
+/// Every component depending on `target`, directly first and then
+/// transitively, each listed once.
+pub fn find_dependents(components: &[LibraryComponent], target: &LibraryComponent) -> Vec<Dependent> {
+    // (dependent, dependency, range) for every internal dependency in the
+    // library; references to missing components have no dependents to report
+    let edges: Vec<(String, String, String)> = library::walk_dependencies(components, components, |_| true)
+        .edges
+        .iter()
+        .filter_map(|edge| {
+            let dependency = edge.dependency?;
+            Some((edge.dependent.id(), dependency.id(), split_internal(&edge.reference).1.to_string()))
+        })
+        .collect();
+
+    let mut dependents: Vec<Dependent> = Vec::new();
+    let mut seen = BTreeSet::from([target.id()]);
+    let mut queue = vec![target.id()];
+    while !queue.is_empty() {
+        let mut next = Vec::new();
+        for dependency in &queue {
+            for (dependent, _, range) in edges.iter().filter(|(_, d, _)| d == dependency) {
+                if !seen.insert(dependent.clone()) {
+                    continue;
+                }
+                let direct = *dependency == target.id();
+                dependents.push(Dependent {
+                    id: dependent.clone(),
+                    range: direct.then(|| range.clone()),
+                    via: (!direct).then(|| dependency.clone()),
+                });
+                next.push(dependent.clone());
+            }
+        }
+        queue = next;
+    }
+    dependents
+}
+
+/// Lists the components depending on `name`, and with `projects` the
+/// configured consumer projects holding imported copies of them.
+pub fn show_dependents(name: &str, projects: bool) -> Result<()> {
+    let config = get_config().map_err(anyhow::Error::msg)?;
+    let components = library::scan(Path::new(&config.components_dir));
+    let target = library::find(&components, name)?;
+    let version = target.metadata().map(|m| m.version);
+    let dependents = find_dependents(&components, target);
+
+    match &version {
+        Some(version) => println!("Components depending on {} (version {}):", target.id(), version),
+        None => println!("Components depending on {}:", target.id()),
+    }
+    if dependents.is_empty() {
+        println!("  {} No component depends on {}", "ℹ".blue().bold(), target.name);
+    }
+    let current = version.as_deref().and_then(|v| parse_version(v).ok());
+    let width = dependents.iter().map(|d| d.id.len()).max().unwrap_or(0);
+    for dependent in &dependents {
+        match (&dependent.range, &dependent.via) {
+            (Some(range), _) => {
+                let accepted = match (Range::parse(range), &current) {
+                    (Ok(parsed), Some(current)) if !parsed.satisfies(current) => {
+                        format!("{} excludes the current version", "⚠".yellow().bold())
+                    }
+                    _ => "✓".green().bold().to_string(),
+                };
+                println!("  {:<width$}  requires {}  {}", dependent.id, range, accepted);
+            }
+            (None, Some(via)) => println!("  {:<width$}  via {}", dependent.id, via),
+            (None, None) => println!("  {}", dependent.id),
+        }
+    }
+
+    if projects {
+        let ids: Vec<String> = std::iter::once(target.id()).chain(dependents.iter().map(|d| d.id.clone())).collect();
+        let names: Vec<&str> = ids.iter().filter_map(|id| id.rsplit('/').next()).collect();
+        println!();
+        if config.projects.is_empty() {
+            println!("{} No projects are configured, list them under `projects` in .component-manager.toml", "ℹ".blue().bold());
+        }
+        let base = ProjectConfig::dir();
+        for project in &config.projects {
+            report_project(&base, project, &ids, &names, &components);
+        }
+    }
+    Ok(())
+}
+
+/// Prints the copies of the `ids` components imported into `project`,
+/// relative to `base` unless absolute. The lockfile maps renamed copies back
+/// to their source; projects without one are searched for files named like
+/// the library's.
+fn report_project(base: &Path, project: &Path, ids: &[String], names: &[&str], components: &[LibraryComponent]) {
+    let dir = base.join(project);
+    if !dir.is_dir() {
+        println!("{} {}: not found", "⚠".yellow().bold(), project.display());
+        return;
+    }
+    let mut copies: Vec<String> = Vec::new();
+    if dir.join(LOCKFILE_NAME).exists() {
+        match Lockfile::load(&dir) {
+            Ok(lockfile) => {
+                for locked in lockfile.components.iter().filter(|c| ids.contains(&c.source)) {
+                    let version = locked.version.as_deref().map(|v| format!(" {}", v)).unwrap_or_default();
+                    let source = if locked.name == locked.source.rsplit('/').next().unwrap_or_default() {
+                        String::new()
+                    } else {
+                        format!(" (from {})", locked.source)
+                    };
+                    copies.push(format!("{}{}{}", locked.name, version, source));
+                }
+            }
+            Err(e) => println!("{} {}: {:#}", "⚠".yellow().bold(), project.display(), e),
+        }
+    } else {
+        let file_names: BTreeSet<String> = components.iter()
+            .filter(|c| ids.contains(&c.id()))
+            .flat_map(|c| c.files.iter())
+            .filter_map(|f| f.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        let walker = walkdir::WalkDir::new(&dir)
+            .into_iter()
+            .filter_entry(|e| !matches!(e.file_name().to_str(), Some("node_modules" | ".git" | "vendor" | "target")));
+        for entry in walker.flatten().filter(|e| e.file_type().is_file()) {
+            let file_name = entry.file_name().to_string_lossy();
+            if file_names.contains(file_name.as_ref()) {
+                let relative = entry.path().strip_prefix(&dir).unwrap_or(entry.path());
+                copies.push(relative.display().to_string());
+            }
+        }
+    }
+
+    if copies.is_empty() {
+        println!("{} {}: no copies of {}", "✓".green().bold(), project.display(), names.join(", "));
+    } else {
+        println!("{} {}:", "⚠".yellow().bold(), project.display());
+        for copy in copies {
+            println!("    {}", copy);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::test_support::component;
+
+    #[test]
+    fn test_find_dependents() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        component(root, "Icon", &[]);
+        component(root, "Button", &["Icon@^1.0.0"]);
+        component(root, "Badge", &["Icon"]);
+        component(root, "Dialog", &["Button"]);
+        component(root, "Toolbar", &["Dialog", "Button"]);
+        let components = library::scan(root);
+        let icon = library::find(&components, "Icon").unwrap();
+
+        let dependents = find_dependents(&components, icon);
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = dependents.iter()
+            .map(|d| (d.id.as_str(), d.range.as_deref(), d.via.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("vue/tailwind/Badge", Some("*"), None),
+                ("vue/tailwind/Button", Some("^1.0.0"), None),
+                ("vue/tailwind/Dialog", None, Some("vue/tailwind/Button")),
+                ("vue/tailwind/Toolbar", None, Some("vue/tailwind/Button")),
+            ]
+        );
+    }
+}
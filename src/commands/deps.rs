@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use inquire::Confirm;
+
+use crate::answers;
+use crate::commands::dependencies::{split_npm, DependencyType, PackageDependency};
+use crate::commands::export::ComponentMetadata;
+use crate::config::get_config;
+use crate::library::{self, LibraryComponent};
+use crate::registry::{Registry, DEFAULT_REGISTRY};
+use crate::semver::{Range, Version};
+
+/// A component requirement that excludes the latest major of its package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outdated {
+    /// `framework/style/name` of the component
+    pub component: String,
+    pub package: String,
+    pub range: String,
+    pub latest: Version,
+}
+
+/// Whether `range` accepts any release of the major version of `latest`.
+fn allows_major(range: &Range, latest: &Version) -> bool {
+    Range::parse(&format!("{}.x", latest.major)).is_ok_and(|major| range.intersect(&major).is_some())
+}
+
+/// Finds the npm requirements in the library that exclude the latest major
+/// version the registry has. Packages the registry cannot tell about are
+/// reported and skipped.
+pub fn find_outdated(components: &[LibraryComponent], registry: &Registry) -> Vec<Outdated> {
+    let mut latest: BTreeMap<String, Option<Version>> = BTreeMap::new();
+    let mut outdated = Vec::new();
+    for component in components {
+        let Some(metadata) = component.metadata() else {
+            continue;
+        };
+        for (requirement, _) in metadata.dependencies.dependencies.iter().filter_map(DependencyType::npm_requirement) {
+            let (name, range) = split_npm(&requirement);
+            let version = latest.entry(name.to_string()).or_insert_with(|| match registry.latest(name) {
+                Ok(version) => Some(version),
+                Err(e) => {
+                    println!("{} {:#}", "⚠".yellow().bold(), e);
+                    None
+                }
+            });
+            let (Some(version), Ok(parsed)) = (version, Range::parse(range)) else {
+                continue;
+            };
+            if !allows_major(&parsed, version) {
+                outdated.push(Outdated {
+                    component: component.id(),
+                    package: name.to_string(),
+                    range: range.to_string(),
+                    latest: version.clone(),
+                });
+            }
+        }
+    }
+    outdated
+}
+
+/// Lists the components whose npm ranges exclude the latest major version
+/// and offers to bump them to `^latest`.
+pub fn deps_outdated(registry_url: Option<&str>, yes: bool) -> Result<()> {
+    let config = get_config().map_err(anyhow::Error::msg)?;
+    let url = registry_url.or(config.registry.as_deref()).unwrap_or(DEFAULT_REGISTRY);
+    let registry = Registry::new(url);
+    let components = library::scan(Path::new(&config.components_dir));
+
+    println!("{} Checking npm dependencies against {}", "ℹ".blue().bold(), registry.url());
+    let outdated = find_outdated(&components, &registry);
+    if outdated.is_empty() {
+        println!("{} Every component accepts the latest major version of its packages", "✓".green().bold());
+        return Ok(());
+    }
+
+    let component_width = outdated.iter().map(|o| o.component.len()).max().unwrap_or(0).max("Component".len());
+    let package_width = outdated.iter().map(|o| o.package.len()).max().unwrap_or(0).max("Package".len());
+    let range_width = outdated.iter().map(|o| o.range.len()).max().unwrap_or(0).max("Range".len());
+    println!("{:<component_width$}  {:<package_width$}  {:<range_width$}  Latest", "Component", "Package", "Range");
+    for o in &outdated {
+        println!("{:<component_width$}  {:<package_width$}  {:<range_width$}  {}", o.component, o.package, o.range, o.latest.to_string().yellow());
+    }
+    println!();
+
+    if !yes {
+        let bump = answers::confirm(
+            "deps.bump",
+            Confirm::new("Bump these ranges to the latest major in component.toml?").with_default(false),
+        )
+        .context("Failed to read confirmation, pass --yes to bump without asking")?;
+        if !bump {
+            println!("Ranges left unchanged");
+            return Ok(());
+        }
+    }
+
+    for component in components.iter().filter(|c| outdated.iter().any(|o| o.component == c.id())) {
+        let bumps: Vec<&Outdated> = outdated.iter().filter(|o| o.component == component.id()).collect();
+        bump_ranges(component, &bumps)?;
+        for o in bumps {
+            println!("{} {}: {} {} → ^{}", "✓".green().bold(), component, o.package, o.range, o.latest);
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites the component's `component.toml` with the outdated packages
+/// required at `^latest`.
+fn bump_ranges(component: &LibraryComponent, outdated: &[&Outdated]) -> Result<()> {
+    let path = component.metadata_path();
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut metadata: ComponentMetadata = toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let dependencies = std::mem::take(&mut metadata.dependencies.dependencies);
+    for dependency in dependencies {
+        let bumped = match &dependency {
+            DependencyType::Package(package) => outdated.iter().find(|o| o.package == package.name).map(|o| {
+                DependencyType::Package(PackageDependency { version: format!("^{}", o.latest), ..package.clone() })
+            }),
+            _ => None,
+        };
+        metadata.dependencies.add_dependency(bumped.unwrap_or(dependency));
+    }
+    metadata.updated_at = Utc::now().to_rfc3339();
+
+    let content = toml::to_string_pretty(&metadata).context("Failed to serialize metadata")?;
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_major() {
+        let latest = Version::new(3, 4, 21);
+        assert!(allows_major(&Range::parse("^3.0.0").unwrap(), &latest));
+        assert!(allows_major(&Range::parse("~3.2.0").unwrap(), &latest));
+        assert!(allows_major(&Range::parse("^2.0.0 || ^3.0.0").unwrap(), &latest));
+        assert!(!allows_major(&Range::parse("^2.6.0").unwrap(), &latest));
+        assert!(!allows_major(&Range::parse("<3.0.0").unwrap(), &latest));
+    }
+}
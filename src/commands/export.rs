@@ -1,39 +1,50 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
-use inquire::{Select, Text};
+use inquire::{Confirm, Select, Text};
 use serde::{Serialize, Deserialize};
-use whoami;
 
+use crate::answers;
 use crate::config::ProjectConfig;
+use crate::library;
+use crate::secrets::SecretScanner;
+use crate::taxonomy::{self, Entry, Taxonomy};
+use crate::utils::expand_glob;
 
-use crate::commands::dependencies::{ComponentDependencies, DependencyType};
+use crate::commands::audit::AcceptedRisk;
+use crate::commands::dependencies::{project_versions, ComponentDependencies};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ComponentMetadata {
-    name: String,
-    version: String,
-    framework: String,
-    style: String,
-    language: String,
-    description: String,
-    author: String,
-    created_at: String,
-    updated_at: String,
-    tags: Vec<String>,
+    pub name: String,
+    pub version: String,
+    pub framework: String,
+    pub style: String,
+    pub language: String,
+    pub description: String,
+    pub author: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub tags: Vec<String>,
+    /// What the component is (e.g. `composable`, `layout`), used to pick its import destination
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
     #[serde(default)]
     pub dependencies: ComponentDependencies,
+    /// Audit findings that were reviewed and accepted, see `audit --accept`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accepted_risks: Vec<AcceptedRisk>,
 }
 
-pub fn export_component() {
+pub fn export_component(allow_secrets: bool) {
     // Get component name (without extension)
-    let name = Text::new("Component name (e.g., Button):")
-        .prompt()
+    let name = answers::text("export.name", Text::new("Component name (e.g., Button):"))
         .expect("Failed to read component name");
 
-    let path_input = Text::new("Path to the existing component file:")
-        .prompt()
+    let path_input = answers::text("export.source_path", Text::new("Path to the existing component file:"))
         .expect("Failed to read source path");
 
     let source_path = PathBuf::from(&path_input);
@@ -43,16 +54,6 @@ pub fn export_component() {
         return;
     }
 
-    let ext = source_path.extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("")
-        .to_lowercase();
-
-    if !crate::utils::SUPPORTED_EXTENSIONS.contains(&ext.as_str()) {
-        eprintln!("❌ Unsupported file type: .{}", ext);
-        return;
-    }
-
     // Read project config
     let project_config: ProjectConfig = match fs::read_to_string(".component-manager.toml")
         .ok()
@@ -63,25 +64,31 @@ pub fn export_component() {
             return;
         }
     };
+    let known = match Taxonomy::load(&project_config.taxonomy) {
+        Ok(known) => known,
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            return;
+        }
+    };
+
+    let ext = file_extension(&source_path);
+
+    if !known.supports_extension(&ext) {
+        eprintln!("❌ Unsupported file type: .{}", ext);
+        return;
+    }
 
     // Get framework and style for the component
-    let framework = Select::new("Select framework:", project_config.framework.clone())
-        .prompt()
+    let framework = answers::select("export.framework", Select::new("Select framework:", project_config.framework.clone()))
         .expect("Failed to select framework");
-	let style = Select::new("Select style:", project_config.style.clone())
-		.prompt()
+	let style = answers::select("export.style", Select::new("Select style:", project_config.style.clone()))
 		.expect("Failed to select style");
-	let destination_dir = Path::new("components").join(framework.clone()).join(style.clone()).join(&name);
-	if let Err(e) = fs::create_dir_all(&destination_dir) {
-		eprintln!("❌ Failed to create destination directory: {}", e);
-		return;
-	}
-
-    let destination = destination_dir.join(format!("{}.{}", name.trim_end_matches(&format!(".{}", ext)), ext));
+	let destination_dir = component_dir(&project_config, &framework, &style, &name);
+    let destination = destination_file(&destination_dir, &name, &ext);
 
     if destination.exists() {
-        let overwrite = Select::new("File already exists. Overwrite?", vec!["Yes", "No"])
-            .prompt()
+        let overwrite = answers::select("export.overwrite", Select::new("File already exists. Overwrite?", vec!["Yes", "No"]))
             .expect("Failed to read selection");
 
         if overwrite == "No" {
@@ -90,63 +97,342 @@ pub fn export_component() {
         }
     }
 
-    // Copy the component file
-    if let Err(e) = fs::copy(&source_path, &destination) {
-        eprintln!("❌ Error exporting component: {}", e);
-        return;
-    }
-
     // Get additional metadata
-    let description = Text::new("Enter a short description for the component:")
-        .with_help_message("This will be shown in the component list")
-        .prompt()
+    let description = answers::text("export.description", Text::new("Enter a short description for the component:")
+        .with_help_message("This will be shown in the component list"))
         .unwrap_or_default();
 
+    let language = project_config.language.first().cloned().unwrap_or_default();
+    let export = ExportPlan { name, source_path, framework, style, language, description, allow_secrets };
+
+    match write_component(&project_config, &known, &export) {
+        Ok(destination) => println!("✅ Successfully exported component to: {}", destination.display()),
+        Err(e) => eprintln!("❌ Error exporting component: {:#}", e),
+    }
+}
+
+/// Everything needed to export one source file into the library.
+struct ExportPlan {
+    name: String,
+    source_path: PathBuf,
+    framework: String,
+    style: String,
+    language: String,
+    description: String,
+    /// Export even when the secret scan finds something
+    allow_secrets: bool,
+}
+
+fn component_dir(project_config: &ProjectConfig, framework: &str, style: &str, name: &str) -> PathBuf {
+    project_config.components_dir.join(framework).join(style).join(name)
+}
+
+fn destination_file(destination_dir: &Path, name: &str, ext: &str) -> PathBuf {
+    destination_dir.join(format!("{}.{}", name.trim_end_matches(&format!(".{}", ext)), ext))
+}
+
+/// Copies the component file into the library and writes its `component.toml`.
+/// When the component already exists its creation date, version, tags and
+/// (if no new one is given) description are kept.
+fn write_component(project_config: &ProjectConfig, known: &Taxonomy, export: &ExportPlan) -> Result<PathBuf> {
+    let mut dependencies = ComponentDependencies::new();
+    dependencies.detect_from_component(&export.source_path, &library::scan(&project_config.components_dir))?;
+
+    // Nothing reaches the library until the exported files are checked
+    let source_dir = export.source_path.parent().unwrap_or(Path::new(""));
+    let files: Vec<PathBuf> = std::iter::once(export.source_path.clone())
+        .chain(dependencies.bundled.iter().map(|file| source_dir.join(file)))
+        .collect();
+    review_secrets(project_config, &export.name, &files, export.allow_secrets)?;
+
+    let ext = file_extension(&export.source_path);
+    let destination_dir = component_dir(project_config, &export.framework, &export.style, &export.name);
+    fs::create_dir_all(&destination_dir)
+        .with_context(|| format!("Failed to create destination directory {}", destination_dir.display()))?;
+
+    // Copy the component file
+    let destination = destination_file(&destination_dir, &export.name, &ext);
+    fs::copy(&export.source_path, &destination)
+        .with_context(|| format!("Failed to copy {}", export.source_path.display()))?;
+
+    let metadata_path = destination_dir.join("component.toml");
+    let existing: Option<ComponentMetadata> = fs::read_to_string(&metadata_path)
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok());
+
     let author = whoami::username();
     let now = Utc::now().to_rfc3339();
 
-    // Create component metadata
-    // Initialize and detect dependencies
-    let mut dependencies = ComponentDependencies::new();
-    // TODO: Implement actual dependency detection
-    // dependencies.detect_from_component(&source_path)?;
-    
-    // Add any framework/plugin dependencies
-    if framework == "vue" {
-        dependencies.add_dependency(DependencyType::Npm("vue@^3.0.0".to_string()));
-    } else if framework == "react" {
-        dependencies.add_dependency(DependencyType::Npm("react@^18.0.0".to_string()));
-        dependencies.add_dependency(DependencyType::Npm("react-dom@^18.0.0".to_string()));
-    }
-    
-    // Add style dependencies
-    if style == "tailwind" {
-        dependencies.add_dependency(DependencyType::Npm("tailwindcss@^3.0.0".to_string()));
+    // The framework and styling runtimes are required even when the
+    // component does not import them directly
+    let versions = project_versions(&export.source_path);
+    let entries = [
+        taxonomy::find(&known.frameworks, &export.framework),
+        taxonomy::find(&known.styles, &export.style),
+        taxonomy::find(&known.languages, &export.language),
+    ];
+    for (package, range) in entries.iter().flatten().flat_map(|entry| &entry.dependencies) {
+        dependencies.add_npm_default(package, range, &versions);
     }
 
+    // Copy the files the component imports, keeping their relative layout
+    for file in &dependencies.bundled {
+        let target = destination_dir.join(file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        fs::copy(source_dir.join(file), &target)
+            .with_context(|| format!("Failed to copy {}", source_dir.join(file).display()))?;
+    }
+
+    let description = match &existing {
+        Some(previous) if export.description.is_empty() => previous.description.clone(),
+        _ => export.description.clone(),
+    };
+
     let metadata = ComponentMetadata {
-        name: name.clone(),
-        version: "0.1.0".to_string(),
-        framework,
-        style,
-        language: project_config.language.first().cloned().unwrap_or_default(),
+        name: export.name.clone(),
+        version: existing.as_ref().map(|m| m.version.clone()).unwrap_or_else(|| "0.1.0".to_string()),
+        framework: export.framework.clone(),
+        style: export.style.clone(),
+        language: export.language.clone(),
         description,
         author,
-        created_at: now.clone(),
+        created_at: existing.as_ref().map(|m| m.created_at.clone()).unwrap_or_else(|| now.clone()),
         updated_at: now,
-        tags: vec![],
+        kind: existing.as_ref().and_then(|m| m.kind.clone()),
+        accepted_risks: existing.as_ref().map(|m| m.accepted_risks.clone()).unwrap_or_default(),
+        tags: existing.map(|m| m.tags).unwrap_or_default(),
         dependencies,
     };
 
     // Write metadata to toml file
-    let metadata_path = destination_dir.join("component.toml");
-    let toml_string = toml::to_string_pretty(&metadata).expect("Failed to serialize metadata");
-    
-    if let Err(e) = fs::write(&metadata_path, toml_string) {
-        eprintln!("⚠️  Warning: Failed to create component metadata: {}", e);
-    } else {
-        println!("✅ Created component metadata at: {}", metadata_path.display());
+    let toml_string = toml::to_string_pretty(&metadata).context("Failed to serialize metadata")?;
+    fs::write(&metadata_path, toml_string)
+        .with_context(|| format!("Failed to create component metadata at {}", metadata_path.display()))?;
+    println!("✅ Created component metadata at: {}", metadata_path.display());
+
+    Ok(destination)
+}
+
+/// Scans the files about to be exported for credentials and internal
+/// references. Findings block the export unless `allow` is set or the user
+/// explicitly confirms; the report only shows redacted values.
+fn review_secrets(project_config: &ProjectConfig, name: &str, files: &[PathBuf], allow: bool) -> Result<()> {
+    let scanner = SecretScanner::new(&project_config.secrets)?;
+    let findings = scanner.scan_files(files);
+    if findings.is_empty() {
+        return Ok(());
+    }
+
+    println!("⚠️  {} may contain secrets or internal references:", name);
+    for finding in &findings {
+        println!("  {}:{}  {}: {}", finding.path.display(), finding.line, finding.kind, finding.redacted);
+        println!("      {}", finding.excerpt);
+    }
+    if allow {
+        println!("Exporting anyway (--allow-secrets)");
+        return Ok(());
+    }
+    let proceed = answers::confirm(
+        "export.allow_secrets",
+        Confirm::new(&format!("Publish {} to the library anyway?", name))
+            .with_default(false)
+            .with_help_message("Remove the values, or list them under [secrets] allow in .component-manager.toml"),
+    )
+    .context("Failed to read confirmation, pass --allow-secrets to export anyway")?;
+    if !proceed {
+        bail!("Export of {} blocked: {} possible secret(s) found", name, findings.len());
+    }
+    Ok(())
+}
+
+fn file_extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+/// Exports every file matching `pattern` in one pass.
+///
+/// Component names come from the file names, and framework, style and language
+/// are inferred per file from the options in the project config. A review
+/// table is shown before anything is written.
+pub fn export_components_by_glob(pattern: &str, allow_secrets: bool) -> Result<()> {
+    let project_config = ProjectConfig::load_from_file()
+        .context("Failed to load or parse `.component-manager.toml`")?;
+    let known = Taxonomy::load(&project_config.taxonomy)?;
+
+    let files: Vec<PathBuf> = expand_glob(pattern)?
+        .into_iter()
+        .filter(|path| known.supports_extension(&file_extension(path)))
+        .collect();
+    if files.is_empty() {
+        bail!("No supported component files match `{}`", pattern);
+    }
+
+    let plans: Vec<ExportPlan> = files
+        .into_iter()
+        .map(|source_path| {
+            let ext = file_extension(&source_path);
+            let content = fs::read_to_string(&source_path).unwrap_or_default();
+            ExportPlan {
+                name: source_path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string(),
+                framework: infer_framework(&ext, &project_config.framework, &known.frameworks),
+                style: infer_style(&content, &project_config.style, &known.styles),
+                language: infer_language(&ext, &content, &project_config.language, &known.languages),
+                description: String::new(),
+                allow_secrets,
+                source_path,
+            }
+        })
+        .collect();
+
+    // Files with the same name would overwrite each other in the library
+    let mut targets: BTreeMap<PathBuf, Vec<&Path>> = BTreeMap::new();
+    for plan in &plans {
+        targets.entry(component_dir(&project_config, &plan.framework, &plan.style, &plan.name))
+            .or_default()
+            .push(&plan.source_path);
+    }
+    let duplicates: Vec<String> = targets.iter()
+        .filter(|(_, sources)| sources.len() > 1)
+        .map(|(target, sources)| {
+            let sources: Vec<String> = sources.iter().map(|s| s.display().to_string()).collect();
+            format!("{} ({})", target.display(), sources.join(", "))
+        })
+        .collect();
+    if !duplicates.is_empty() {
+        bail!("Several files would be exported to the same component: {}", duplicates.join("; "));
     }
 
-    println!("✅ Successfully exported component to: {}", destination.display());
+    let name_width = plans.iter().map(|p| p.name.len()).max().unwrap_or(0).max("Component".len());
+    let stack_width = plans.iter()
+        .map(|p| p.framework.len() + p.style.len() + 1)
+        .max()
+        .unwrap_or(0)
+        .max("Stack".len());
+    println!("{:<name_width$}  {:<stack_width$}  {:<6}  Source", "Component", "Stack", "Status");
+    for plan in &plans {
+        let exists = component_dir(&project_config, &plan.framework, &plan.style, &plan.name).exists();
+        println!(
+            "{:<name_width$}  {:<stack_width$}  {:<6}  {}",
+            plan.name,
+            format!("{}/{}", plan.framework, plan.style),
+            if exists { "update" } else { "new" },
+            plan.source_path.display(),
+        );
+    }
+
+    let proceed = answers::confirm(
+        "export.confirm_batch",
+        Confirm::new(&format!("Export {} components?", plans.len())).with_default(true),
+    )?;
+    if !proceed {
+        println!("❌ Export cancelled.");
+        return Ok(());
+    }
+
+    let mut failures = Vec::new();
+    for plan in &plans {
+        match write_component(&project_config, &known, plan) {
+            Ok(destination) => println!("✅ Exported {} to: {}", plan.name, destination.display()),
+            Err(e) => {
+                eprintln!("❌ Failed to export {}: {:#}", plan.name, e);
+                failures.push(plan.name.as_str());
+            }
+        }
+    }
+
+    println!(
+        "\n{} exported, {} failed",
+        plans.len() - failures.len(),
+        failures.len()
+    );
+    if !failures.is_empty() {
+        bail!("Failed to export: {}", failures.join(", "));
+    }
+    Ok(())
+}
+
+/// The configured entries, in taxonomy order followed by configured ids the
+/// taxonomy does not know.
+fn configured<'a>(configured: &'a [String], known: &'a [Entry]) -> Vec<(&'a String, Option<&'a Entry>)> {
+    let mut entries: Vec<(&String, Option<&Entry>)> = configured.iter()
+        .map(|id| (id, taxonomy::find(known, id)))
+        .collect();
+    entries.sort_by_key(|(id, _)| taxonomy::position(known, id));
+    entries
+}
+
+/// Picks the configured framework whose extensions include the file's,
+/// falling back to the first configured framework.
+fn infer_framework(ext: &str, frameworks: &[String], known: &[Entry]) -> String {
+    configured(frameworks, known).into_iter()
+        .find(|(_, entry)| entry.is_some_and(|e| e.extensions.iter().any(|x| x == ext)))
+        .map(|(id, _)| id)
+        .or_else(|| frameworks.first())
+        .cloned()
+        .unwrap_or_else(|| "none".to_string())
+}
+
+/// Picks the configured style with the most markers in the source, falling
+/// back to the first configured style.
+fn infer_style(content: &str, styles: &[String], known: &[Entry]) -> String {
+    let mut best: Option<(&String, usize)> = None;
+    for (id, entry) in configured(styles, known) {
+        let found = entry.map_or(0, |e| e.markers.iter().filter(|m| content.contains(m.as_str())).count());
+        if found > 0 && best.is_none_or(|(_, most)| found > most) {
+            best = Some((id, found));
+        }
+    }
+    best.map(|(id, _)| id)
+        .or_else(|| styles.first())
+        .cloned()
+        .unwrap_or_else(|| "none".to_string())
+}
+
+/// Picks the configured language whose markers appear in the source, then
+/// the one whose extensions include the file's, otherwise the first
+/// configured language.
+pub(crate) fn infer_language(ext: &str, content: &str, languages: &[String], known: &[Entry]) -> String {
+    let candidates = configured(languages, known);
+    candidates.iter()
+        .find(|(_, entry)| entry.is_some_and(|e| e.markers.iter().any(|m| content.contains(m.as_str()))))
+        .or_else(|| candidates.iter().find(|(_, entry)| entry.is_some_and(|e| e.extensions.iter().any(|x| x == ext))))
+        .map(|(id, _)| *id)
+        .or_else(|| languages.first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_stack() {
+        let frameworks = vec!["react".to_string(), "vue".to_string()];
+        let styles = vec!["none".to_string(), "tailwind".to_string()];
+        let languages = vec!["javascript".to_string(), "typescript".to_string()];
+
+        let known = Taxonomy::builtin();
+
+        assert_eq!(infer_framework("vue", &frameworks, &known.frameworks), "vue");
+        assert_eq!(infer_framework("tsx", &frameworks, &known.frameworks), "react");
+        assert_eq!(infer_framework("css", &frameworks, &known.frameworks), "react");
+        assert_eq!(infer_style(r#"<button class="px-4 bg-blue-500">"#, &styles, &known.styles), "tailwind");
+        assert_eq!(infer_style("<button>", &styles, &known.styles), "none");
+        assert_eq!(infer_language("tsx", "", &languages, &known.languages), "typescript");
+        assert_eq!(infer_language("vue", "<script setup>", &languages, &known.languages), "javascript");
+        assert_eq!(infer_language("vue", "<script setup lang=\"ts\">", &languages, &known.languages), "typescript");
+
+        let mut known = known;
+        known.merge(&toml::from_str("[styles.styled-components]\nmarkers = [\"styled.\", \"from 'styled-components'\"]").unwrap());
+        let styles = vec!["tailwind".to_string(), "styled-components".to_string()];
+        let source = "import styled from 'styled-components'\nconst Box = styled.div`display: flex `";
+        assert_eq!(infer_style(source, &styles, &known.styles), "styled-components");
+    }
 }
@@ -0,0 +1,475 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use inquire::{Confirm, MultiSelect};
+
+use crate::answers;
+use crate::commands::dependencies::ComponentDependencies;
+use crate::commands::export::{infer_language, ComponentMetadata};
+use crate::config::get_config;
+use crate::library;
+use crate::taxonomy::Taxonomy;
+
+/// Something wrong with the layout of the component library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// A component folder without `component.toml`
+    MissingMetadata { dir: PathBuf },
+    /// A `component.toml` that does not parse as component metadata
+    InvalidMetadata { dir: PathBuf, error: String },
+    /// `name`, `framework` or `style` in `component.toml` disagreeing with
+    /// the folder the component is in
+    Mismatch { dir: PathBuf, field: &'static str, expected: String, found: String },
+    /// A file outside the `framework/style/name` layout
+    OrphanFile { path: PathBuf },
+    /// A directory without any file in it
+    EmptyDir { path: PathBuf },
+    /// Components of one framework and style sharing a name, ignoring case
+    Duplicate { dir: PathBuf, name: String, paths: Vec<PathBuf> },
+}
+
+impl Problem {
+    pub fn path(&self) -> &Path {
+        match self {
+            Problem::MissingMetadata { dir }
+            | Problem::InvalidMetadata { dir, .. }
+            | Problem::Mismatch { dir, .. }
+            | Problem::Duplicate { dir, .. } => dir,
+            Problem::OrphanFile { path } | Problem::EmptyDir { path } => path,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::MissingMetadata { .. } => write!(f, "component folder without component.toml"),
+            Problem::InvalidMetadata { error, .. } => write!(f, "invalid component.toml: {}", error),
+            Problem::Mismatch { field, expected, found, .. } => {
+                write!(f, "component.toml has {} = \"{}\" but the folder says \"{}\"", field, found, expected)
+            }
+            Problem::OrphanFile { .. } => write!(f, "file outside the framework/style/name layout"),
+            Problem::EmptyDir { .. } => write!(f, "empty directory"),
+            Problem::Duplicate { name, paths, .. } => {
+                let names: Vec<String> = paths.iter()
+                    .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                    .collect();
+                write!(f, "duplicate component name \"{}\": {}", name, names.join(", "))
+            }
+        }
+    }
+}
+
+/// Checks every directory of the library against the
+/// `<framework>/<style>/<name>/component.toml` layout.
+pub fn check_library(components_dir: &Path) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    // Only the outermost empty directory is reported
+    let mut empty: Vec<PathBuf> = Vec::new();
+    for entry in walkdir::WalkDir::new(components_dir).min_depth(1).sort_by_file_name().into_iter().flatten() {
+        if entry.file_type().is_dir() && !empty.iter().any(|e| entry.path().starts_with(e)) && !has_files(entry.path()) {
+            empty.push(entry.path().to_path_buf());
+        }
+    }
+    problems.extend(empty.iter().map(|path| Problem::EmptyDir { path: path.clone() }));
+    let is_empty = |path: &Path| empty.iter().any(|e| e == path);
+
+    for (framework_dir, framework_files) in listing(components_dir) {
+        let Some(framework_dir) = framework_dir else {
+            problems.extend(framework_files.into_iter().map(|path| Problem::OrphanFile { path }));
+            continue;
+        };
+        if is_empty(&framework_dir) {
+            continue;
+        }
+        for (style_dir, style_files) in listing(&framework_dir) {
+            let Some(style_dir) = style_dir else {
+                problems.extend(style_files.into_iter().map(|path| Problem::OrphanFile { path }));
+                continue;
+            };
+            if is_empty(&style_dir) {
+                continue;
+            }
+            let framework = file_name(&framework_dir);
+            let style = file_name(&style_dir);
+
+            // Lowercased name → the components claiming it
+            let mut names: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+            for (component_dir, files) in listing(&style_dir) {
+                let Some(component_dir) = component_dir else {
+                    // Components are folders; a file at this level belongs to none
+                    problems.extend(files.into_iter().map(|path| Problem::OrphanFile { path }));
+                    continue;
+                };
+                if is_empty(&component_dir) {
+                    continue;
+                }
+                let name = file_name(&component_dir);
+                names.entry(name.to_lowercase()).or_default().push(component_dir.clone());
+
+                let metadata_path = component_dir.join("component.toml");
+                let Ok(content) = fs::read_to_string(&metadata_path) else {
+                    problems.push(Problem::MissingMetadata { dir: component_dir });
+                    continue;
+                };
+                let metadata = match toml::from_str::<ComponentMetadata>(&content) {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        // Syntax errors only describe themselves through Display
+                        let error = match e.message() {
+                            "" => e.to_string().lines().next().unwrap_or_default().to_string(),
+                            message => message.to_string(),
+                        };
+                        problems.push(Problem::InvalidMetadata { dir: component_dir, error });
+                        continue;
+                    }
+                };
+                if metadata.name.to_lowercase() != name.to_lowercase() {
+                    let claimed = names.entry(metadata.name.to_lowercase()).or_default();
+                    if !claimed.contains(&component_dir) {
+                        claimed.push(component_dir.clone());
+                    }
+                }
+                for (field, expected, found) in [
+                    ("name", &name, &metadata.name),
+                    ("framework", &framework, &metadata.framework),
+                    ("style", &style, &metadata.style),
+                ] {
+                    if expected != found {
+                        problems.push(Problem::Mismatch {
+                            dir: component_dir.clone(),
+                            field,
+                            expected: expected.clone(),
+                            found: found.clone(),
+                        });
+                    }
+                }
+            }
+
+            for (name, mut paths) in names.into_iter().filter(|(_, paths)| paths.len() > 1) {
+                paths.sort();
+                problems.push(Problem::Duplicate { dir: style_dir.clone(), name, paths });
+            }
+        }
+    }
+    problems
+}
+
+/// The subdirectories of `dir` with `None` for its files, sorted and
+/// without hidden entries such as `.gitkeep`.
+fn listing(dir: &Path) -> Vec<(Option<PathBuf>, Vec<PathBuf>)> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect();
+    entries.sort();
+    let files: Vec<PathBuf> = entries.iter().filter(|p| p.is_file()).cloned().collect();
+    let mut listing: Vec<(Option<PathBuf>, Vec<PathBuf>)> = entries.into_iter()
+        .filter(|p| p.is_dir())
+        .map(|p| (Some(p), Vec::new()))
+        .collect();
+    if !files.is_empty() {
+        listing.push((None, files));
+    }
+    listing
+}
+
+fn has_files(dir: &Path) -> bool {
+    walkdir::WalkDir::new(dir).into_iter().flatten().any(|e| e.file_type().is_file())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string()
+}
+
+/// Checks the component library, and with `repair` offers to fix what can be
+/// fixed: removing empty directories, generating missing or invalid
+/// metadata, and reconciling metadata with the folder it is in.
+pub fn fsck(repair: bool) -> Result<()> {
+    let config = get_config().map_err(anyhow::Error::msg)?;
+    let components_dir = config.components_dir.clone();
+    if !components_dir.is_dir() {
+        bail!("Components directory not found at: {}", components_dir.display());
+    }
+
+    let problems = check_library(&components_dir);
+    if problems.is_empty() {
+        println!("{} No problems found in {}", "✓".green().bold(), components_dir.display());
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("{} {}: {}", "✗".red().bold(), problem.path().display(), problem);
+    }
+    println!();
+    println!("{} problem(s) found", problems.len());
+    if !repair {
+        bail!("The component library has {} problem(s), run `fsck --repair` to fix them", problems.len());
+    }
+
+    println!();
+    let known = Taxonomy::load(&config.taxonomy)?;
+    let repaired = repair_problems(&problems, &components_dir, &config.language, &known)?;
+    println!("{} Repaired {} problem(s)", "✓".green().bold(), repaired);
+
+    let remaining = check_library(&components_dir);
+    if !remaining.is_empty() {
+        bail!("{} problem(s) remain", remaining.len());
+    }
+    Ok(())
+}
+
+fn repair_problems(problems: &[Problem], components_dir: &Path, languages: &[String], known: &Taxonomy) -> Result<usize> {
+    let mut repaired = 0;
+
+    let empty: Vec<&Path> = problems.iter()
+        .filter_map(|p| match p {
+            Problem::EmptyDir { path } => Some(path.as_path()),
+            _ => None,
+        })
+        .collect();
+    if !empty.is_empty() {
+        let remove = answers::confirm(
+            "fsck.remove_empty",
+            Confirm::new(&format!("Remove {} empty director(ies)?", empty.len())).with_default(false),
+        )
+        .context("Failed to read confirmation")?;
+        if remove {
+            for dir in &empty {
+                fs::remove_dir_all(dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+                println!("  - removed {}", dir.display());
+                repaired += 1;
+            }
+        }
+    }
+
+    let without_metadata: Vec<String> = problems.iter()
+        .filter_map(|p| match p {
+            Problem::MissingMetadata { dir } | Problem::InvalidMetadata { dir, .. } => Some(dir.display().to_string()),
+            _ => None,
+        })
+        .collect();
+    if !without_metadata.is_empty() {
+        let selected = answers::multi_select(
+            "fsck.generate",
+            MultiSelect::new("Generate component.toml for:", without_metadata),
+        )?;
+        for dir in selected.iter().map(PathBuf::from) {
+            let metadata_path = dir.join("component.toml");
+            if metadata_path.exists() {
+                let backup = dir.join("component.toml.bak");
+                fs::rename(&metadata_path, &backup)
+                    .with_context(|| format!("Failed to back up {}", metadata_path.display()))?;
+                println!("  - kept the invalid metadata as {}", backup.display());
+            }
+            let (metadata, warnings) = generate_metadata(&dir, languages, known, backup_table(&dir));
+            let toml_string = toml::to_string_pretty(&metadata).context("Failed to serialize metadata")?;
+            fs::write(&metadata_path, toml_string)
+                .with_context(|| format!("Failed to write {}", metadata_path.display()))?;
+            println!("  - generated {}", metadata_path.display());
+            for warning in warnings {
+                println!("    {} {}", "⚠".yellow().bold(), warning);
+            }
+            repaired += 1;
+        }
+    }
+
+    let mut mismatched: Vec<String> = problems.iter()
+        .filter_map(|p| match p {
+            Problem::Mismatch { dir, .. } => Some(dir.display().to_string()),
+            _ => None,
+        })
+        .collect();
+    mismatched.dedup();
+    if !mismatched.is_empty() {
+        let rewrite = answers::multi_select(
+            "fsck.update_metadata",
+            MultiSelect::new("Rewrite component.toml to match its folder:", mismatched.clone()),
+        )?;
+        for dir in rewrite.iter().map(PathBuf::from) {
+            let (mut metadata, metadata_path) = read_metadata(&dir)?;
+            metadata.name = file_name(&dir);
+            metadata.style = dir.parent().map(file_name).unwrap_or_default();
+            metadata.framework = dir.parent().and_then(Path::parent).map(file_name).unwrap_or_default();
+            metadata.updated_at = Utc::now().to_rfc3339();
+            let toml_string = toml::to_string_pretty(&metadata).context("Failed to serialize metadata")?;
+            fs::write(&metadata_path, toml_string)
+                .with_context(|| format!("Failed to write {}", metadata_path.display()))?;
+            println!("  - updated {}", metadata_path.display());
+            repaired += 1;
+        }
+
+        let rest: Vec<String> = mismatched.into_iter().filter(|d| !rewrite.contains(d)).collect();
+        if !rest.is_empty() {
+            let relocate = answers::multi_select(
+                "fsck.relocate",
+                MultiSelect::new("Move to the location in component.toml:", rest),
+            )?;
+            for dir in relocate.iter().map(PathBuf::from) {
+                let (metadata, _) = read_metadata(&dir)?;
+                let target = components_dir.join(&metadata.framework).join(&metadata.style).join(&metadata.name);
+                if target.exists() {
+                    println!("  {} {} already exists, {} left in place", "⚠".yellow().bold(), target.display(), dir.display());
+                    continue;
+                }
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+                }
+                fs::rename(&dir, &target)
+                    .with_context(|| format!("Failed to move {} to {}", dir.display(), target.display()))?;
+                println!("  - moved {} to {}", dir.display(), target.display());
+                repaired += 1;
+            }
+        }
+    }
+
+    let manual = problems.iter()
+        .filter(|p| matches!(p, Problem::OrphanFile { .. } | Problem::Duplicate { .. }))
+        .count();
+    if manual > 0 {
+        println!("{} {} problem(s) need to be resolved by hand", "ℹ".blue().bold(), manual);
+    }
+    Ok(repaired)
+}
+
+fn read_metadata(dir: &Path) -> Result<(ComponentMetadata, PathBuf)> {
+    let metadata_path = dir.join("component.toml");
+    let content = fs::read_to_string(&metadata_path)
+        .with_context(|| format!("Failed to read {}", metadata_path.display()))?;
+    let metadata = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", metadata_path.display()))?;
+    Ok((metadata, metadata_path))
+}
+
+/// The readable part of a backed-up invalid `component.toml`.
+fn backup_table(dir: &Path) -> toml::Table {
+    fs::read_to_string(dir.join("component.toml.bak"))
+        .ok()
+        .and_then(|content| content.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Metadata for the component in `dir`, named after the folder it is in.
+/// Fields that can be read from `previous` are kept. Problems detecting the
+/// dependencies are returned as warnings, the metadata is usable without them.
+fn generate_metadata(dir: &Path, languages: &[String], known: &Taxonomy, previous: toml::Table) -> (ComponentMetadata, Vec<String>) {
+    let name = file_name(dir);
+    let main_file = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| known.supports_extension(&e.to_string_lossy().to_lowercase())))
+        .min_by_key(|p| p.file_stem().and_then(|s| s.to_str()) != Some(name.as_str()));
+    let language = main_file.as_ref()
+        .map(|file| {
+            let ext = file.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+            infer_language(&ext, &fs::read_to_string(file).unwrap_or_default(), languages, &known.languages)
+        })
+        .unwrap_or_default();
+    let mut dependencies = ComponentDependencies::default();
+    let mut warnings = Vec::new();
+    if let Some(file) = &main_file {
+        let library = dir.ancestors().nth(3).map(library::scan).unwrap_or_default();
+        match dependencies.collect_from_component(file, &library) {
+            Ok(notices) => warnings = notices,
+            Err(e) => warnings.push(format!("dependencies not detected: {:#}", e)),
+        }
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let kept = |field: &str, default: String| {
+        previous.get(field).and_then(|v| v.as_str()).map(str::to_string).unwrap_or(default)
+    };
+    let metadata = ComponentMetadata {
+        version: kept("version", "0.1.0".to_string()),
+        framework: dir.parent().and_then(Path::parent).map(file_name).unwrap_or_default(),
+        style: dir.parent().map(file_name).unwrap_or_default(),
+        language: kept("language", language),
+        description: kept("description", String::new()),
+        author: kept("author", String::new()),
+        created_at: kept("created_at", now.clone()),
+        updated_at: now,
+        tags: previous.get("tags")
+            .and_then(|v| v.as_array())
+            .map(|tags| tags.iter().filter_map(|t| t.as_str().map(str::to_string)).collect())
+            .unwrap_or_default(),
+        kind: previous.get("kind").and_then(|v| v.as_str()).map(str::to_string),
+        name,
+        dependencies,
+        accepted_risks: Vec::new(),
+    };
+    (metadata, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(name: &str, framework: &str, style: &str) -> String {
+        format!(
+            "name = \"{name}\"\nversion = \"0.1.0\"\nframework = \"{framework}\"\nstyle = \"{style}\"\nlanguage = \"javascript\"\n\
+             description = \"\"\nauthor = \"\"\ncreated_at = \"\"\nupdated_at = \"\"\ntags = []\n"
+        )
+    }
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_check_library() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let style = root.join("vue").join("tailwind");
+        write(&style.join("Button/Button.vue"), "<template />");
+        write(&style.join("Button/component.toml"), &metadata("Button", "vue", "tailwind"));
+        write(&style.join("Card/Card.vue"), "<template />");
+        write(&style.join("Card/component.toml"), &metadata("Button", "react", "tailwind"));
+        write(&style.join("Modal/Modal.vue"), "<script setup>\nimport Overlay from '@/components/Overlay.vue'\n</script>\n");
+        write(&style.join("Broken/Broken.vue"), "<template />");
+        write(&style.join("Broken/component.toml"), "name = \"Broken\"\n");
+        write(&style.join(".gitkeep"), "");
+        write(&style.join("README.md"), "");
+        write(&style.join("Button.vue"), "<template />");
+        write(&root.join("vue/notes.md"), "");
+        fs::create_dir_all(style.join("Empty/assets")).unwrap();
+        fs::create_dir_all(root.join("react/bootstrap")).unwrap();
+
+        let problems = check_library(root);
+        let summary: Vec<String> = problems.iter()
+            .map(|p| format!("{}: {}", p.path().strip_prefix(root).unwrap().display(), p))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                "react: empty directory",
+                "vue/tailwind/Empty: empty directory",
+                "vue/tailwind/Broken: invalid component.toml: missing field `version`",
+                "vue/tailwind/Card: component.toml has name = \"Button\" but the folder says \"Card\"",
+                "vue/tailwind/Card: component.toml has framework = \"react\" but the folder says \"vue\"",
+                "vue/tailwind/Modal: component folder without component.toml",
+                "vue/tailwind/Button.vue: file outside the framework/style/name layout",
+                "vue/tailwind/README.md: file outside the framework/style/name layout",
+                "vue/tailwind: duplicate component name \"button\": Button, Card",
+                "vue/notes.md: file outside the framework/style/name layout",
+            ]
+        );
+
+        let (generated, warnings) = generate_metadata(&style.join("Modal"), &["javascript".to_string()], &Taxonomy::builtin(), toml::Table::new());
+        assert_eq!((generated.name.as_str(), generated.framework.as_str(), generated.style.as_str()), ("Modal", "vue", "tailwind"));
+        assert_eq!(generated.language, "javascript");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].ends_with("imports @/components/Overlay.vue, which is not in the library and will not be exported"), "got: {:?}", warnings);
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+use crate::config::get_config;
+use crate::library::{self, LibraryComponent};
+
+/// How `graph` prints the dependency graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    /// An indented tree for the terminal
+    #[default]
+    Ascii,
+    /// Graphviz DOT
+    Dot,
+    Mermaid,
+}
+
+impl GraphFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "ascii" | "tree" => Some(Self::Ascii),
+            "dot" | "graphviz" => Some(Self::Dot),
+            "mermaid" => Some(Self::Mermaid),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Component,
+    /// An internal dependency that is not in the library
+    Missing,
+    /// An npm (or other ecosystem) package, only shown on request
+    Package,
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    /// `framework/style/name` for components, the reference or package otherwise
+    pub id: String,
+    pub kind: NodeKind,
+}
+
+/// The dependencies between library components, as indices into `nodes`.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<Node>,
+    pub edges: BTreeSet<(usize, usize)>,
+}
+
+impl DependencyGraph {
+    /// Builds the graph of `components`, or only of what `root` reaches when
+    /// given. Packages become leaf nodes when `packages` is set.
+    pub fn build(components: &[LibraryComponent], root: Option<&LibraryComponent>, packages: bool) -> Self {
+        let mut graph = Self::default();
+        let walk = match root {
+            Some(root) => library::walk_dependencies(components, [root], |_| true),
+            None => library::walk_dependencies(components, components, |_| true),
+        };
+        for component in &walk.components {
+            graph.node(&component.id(), NodeKind::Component);
+        }
+        for edge in &walk.edges {
+            let from = graph.node(&edge.dependent.id(), NodeKind::Component);
+            let to = match edge.dependency {
+                Some(dependency) => graph.node(&dependency.id(), NodeKind::Component),
+                None => graph.node(&edge.reference, NodeKind::Missing),
+            };
+            graph.edges.insert((from, to));
+        }
+        if packages {
+            for component in &walk.components {
+                let Some(metadata) = component.metadata() else {
+                    continue;
+                };
+                let from = graph.node(&component.id(), NodeKind::Component);
+                for dependency in &metadata.dependencies.dependencies {
+                    let requirement = dependency.npm_requirement().map(|(requirement, _)| requirement)
+                        .or_else(|| dependency.ecosystem_requirement().map(|(_, requirement)| requirement.to_string()));
+                    if let Some(requirement) = requirement {
+                        let to = graph.node(&requirement, NodeKind::Package);
+                        graph.edges.insert((from, to));
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    fn node(&mut self, id: &str, kind: NodeKind) -> usize {
+        if let Some(index) = self.nodes.iter().position(|n| n.id == id && n.kind == kind) {
+            return index;
+        }
+        self.nodes.push(Node { id: id.to_string(), kind });
+        self.nodes.len() - 1
+    }
+
+    fn dependencies(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges.iter().filter(move |(from, _)| *from == node).map(|(_, to)| *to)
+    }
+
+    /// Every elementary cycle, as the nodes along it starting from the
+    /// smallest id. Cycles only exist within a strongly connected component,
+    /// and each one is found once, from its lowest node.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut cycles: Vec<Vec<usize>> = Vec::new();
+        for component in self.strongly_connected() {
+            for &start in &component {
+                let allowed: BTreeSet<usize> = component.iter().copied().filter(|n| *n >= start).collect();
+                self.find_cycles(start, start, &allowed, &mut vec![start], &mut cycles);
+            }
+        }
+        for cycle in &mut cycles {
+            let smallest = (0..cycle.len()).min_by_key(|i| &self.nodes[cycle[*i]].id).unwrap_or(0);
+            cycle.rotate_left(smallest);
+        }
+        cycles.sort_by(|a, b| a.iter().map(|n| &self.nodes[*n].id).cmp(b.iter().map(|n| &self.nodes[*n].id)));
+        cycles
+    }
+
+    /// Extends `path` along the nodes in `allowed`, recording a cycle each
+    /// time it leads back to `start`.
+    fn find_cycles(&self, start: usize, node: usize, allowed: &BTreeSet<usize>, path: &mut Vec<usize>, cycles: &mut Vec<Vec<usize>>) {
+        for next in self.dependencies(node).filter(|n| allowed.contains(n)).collect::<Vec<_>>() {
+            if next == start {
+                cycles.push(path.clone());
+            } else if !path.contains(&next) {
+                path.push(next);
+                self.find_cycles(start, next, allowed, path, cycles);
+                path.pop();
+            }
+        }
+    }
+
+    /// The strongly connected components that can hold a cycle: those of
+    /// several nodes, and single nodes depending on themselves (Tarjan's
+    /// algorithm).
+    fn strongly_connected(&self) -> Vec<Vec<usize>> {
+        struct Tarjan {
+            next_index: usize,
+            index: Vec<Option<usize>>,
+            low_link: Vec<usize>,
+            stack: Vec<usize>,
+            on_stack: Vec<bool>,
+            components: Vec<Vec<usize>>,
+        }
+
+        fn visit(graph: &DependencyGraph, state: &mut Tarjan, node: usize) {
+            state.index[node] = Some(state.next_index);
+            state.low_link[node] = state.next_index;
+            state.next_index += 1;
+            state.stack.push(node);
+            state.on_stack[node] = true;
+            for next in graph.dependencies(node) {
+                match state.index[next] {
+                    None => {
+                        visit(graph, state, next);
+                        state.low_link[node] = state.low_link[node].min(state.low_link[next]);
+                    }
+                    Some(index) if state.on_stack[next] => state.low_link[node] = state.low_link[node].min(index),
+                    Some(_) => {}
+                }
+            }
+            if Some(state.low_link[node]) == state.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = state.stack.pop() {
+                    state.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                state.components.push(component);
+            }
+        }
+
+        let count = self.nodes.len();
+        let mut state = Tarjan {
+            next_index: 0,
+            index: vec![None; count],
+            low_link: vec![0; count],
+            stack: Vec::new(),
+            on_stack: vec![false; count],
+            components: Vec::new(),
+        };
+        for node in 0..count {
+            if state.index[node].is_none() {
+                visit(self, &mut state, node);
+            }
+        }
+        state.components.into_iter()
+            .filter(|component| component.len() > 1 || self.edges.contains(&(component[0], component[0])))
+            .collect()
+    }
+
+    /// Edges that are part of one of `cycles`.
+    fn cycle_edges(cycles: &[Vec<usize>]) -> BTreeSet<(usize, usize)> {
+        cycles.iter()
+            .flat_map(|cycle| (0..cycle.len()).map(move |i| (cycle[i], cycle[(i + 1) % cycle.len()])))
+            .collect()
+    }
+
+    /// The errors of the graph: cycles and internal dependencies that are
+    /// not in the library.
+    pub fn errors(&self) -> Vec<String> {
+        let mut errors: Vec<String> = self.cycles().iter()
+            .map(|cycle| {
+                let names: Vec<&str> = cycle.iter().chain(cycle.first()).map(|n| self.nodes[*n].id.as_str()).collect();
+                format!("Dependency cycle: {}", names.join(" → "))
+            })
+            .collect();
+        for (from, to) in &self.edges {
+            if self.nodes[*to].kind == NodeKind::Missing {
+                errors.push(format!("{} depends on '{}', which is not in the library", self.nodes[*from].id, self.nodes[*to].id));
+            }
+        }
+        errors
+    }
+
+    pub fn to_dot(&self) -> String {
+        let in_cycle = Self::cycle_edges(&self.cycles());
+        let mut out = String::from("digraph components {\n    rankdir=LR;\n    node [shape=box];\n");
+        for node in &self.nodes {
+            let attributes = match node.kind {
+                NodeKind::Component => String::new(),
+                NodeKind::Missing => " [color=red, fontcolor=red, style=dashed, label=\"".to_string() + &dot_escape(&node.id) + " (missing)\"]",
+                NodeKind::Package => " [shape=ellipse, color=gray]".to_string(),
+            };
+            let _ = writeln!(out, "    \"{}\"{};", dot_escape(&node.id), attributes);
+        }
+        for edge @ (from, to) in &self.edges {
+            let attributes = if in_cycle.contains(edge) || self.nodes[*to].kind == NodeKind::Missing {
+                " [color=red]"
+            } else {
+                ""
+            };
+            let _ = writeln!(out, "    \"{}\" -> \"{}\"{};", dot_escape(&self.nodes[*from].id), dot_escape(&self.nodes[*to].id), attributes);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let in_cycle = Self::cycle_edges(&self.cycles());
+        let mut out = String::from("graph LR\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = node.id.replace('"', "#quot;");
+            let _ = match node.kind {
+                NodeKind::Component => writeln!(out, "    n{}[\"{}\"]", index, label),
+                NodeKind::Missing => writeln!(out, "    n{}[\"{} (missing)\"]:::error", index, label),
+                NodeKind::Package => writeln!(out, "    n{}([\"{}\"])", index, label),
+            };
+        }
+        let mut highlighted = Vec::new();
+        for (position, edge @ (from, to)) in self.edges.iter().enumerate() {
+            let _ = writeln!(out, "    n{} --> n{}", from, to);
+            if in_cycle.contains(edge) || self.nodes[*to].kind == NodeKind::Missing {
+                highlighted.push(position.to_string());
+            }
+        }
+        if self.nodes.iter().any(|n| n.kind == NodeKind::Missing) {
+            out.push_str("    classDef error stroke:#d00,color:#d00,stroke-dasharray:4\n");
+        }
+        if !highlighted.is_empty() {
+            let _ = writeln!(out, "    linkStyle {} stroke:#d00", highlighted.join(","));
+        }
+        out
+    }
+
+    /// An indented tree per root, i.e. per component nothing else depends
+    /// on. Components only reachable through a cycle are roots as well.
+    pub fn to_ascii(&self, root: Option<usize>) -> String {
+        let mut roots: Vec<usize> = match root {
+            Some(root) => vec![root],
+            None => (0..self.nodes.len())
+                .filter(|n| self.nodes[*n].kind == NodeKind::Component && !self.edges.iter().any(|(_, to)| to == n))
+                .collect(),
+        };
+        roots.sort_by_key(|r| &self.nodes[*r].id);
+        let mut printed = BTreeSet::new();
+        let mut out = String::new();
+        loop {
+            for root in &roots {
+                self.ascii_node(*root, "", "", &mut Vec::new(), &mut printed, &mut out);
+            }
+            let unprinted = (0..self.nodes.len())
+                .filter(|n| self.nodes[*n].kind == NodeKind::Component && !printed.contains(n))
+                .min_by_key(|n| &self.nodes[*n].id);
+            match unprinted {
+                Some(unprinted) if root.is_none() => roots = vec![unprinted],
+                _ => break,
+            }
+        }
+        out
+    }
+
+    fn ascii_node(&self, node: usize, prefix: &str, child_prefix: &str, path: &mut Vec<usize>, printed: &mut BTreeSet<usize>, out: &mut String) {
+        let label = match self.nodes[node].kind {
+            NodeKind::Component => self.nodes[node].id.clone(),
+            NodeKind::Missing => format!("{} {}", self.nodes[node].id, "(missing)".red().bold()),
+            NodeKind::Package => self.nodes[node].id.dimmed().to_string(),
+        };
+        if path.contains(&node) {
+            let _ = writeln!(out, "{}{} {}", prefix, label, "↻ cycle".red().bold());
+            return;
+        }
+        let _ = writeln!(out, "{}{}", prefix, label);
+        printed.insert(node);
+        path.push(node);
+        let mut children: Vec<usize> = self.dependencies(node).collect();
+        children.sort_by_key(|c| &self.nodes[*c].id);
+        for (i, child) in children.iter().enumerate() {
+            let last = i + 1 == children.len();
+            let branch = format!("{}{}", child_prefix, if last { "└── " } else { "├── " });
+            let continuation = format!("{}{}", child_prefix, if last { "    " } else { "│   " });
+            self.ascii_node(*child, &branch, &continuation, path, printed, out);
+        }
+        path.pop();
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Prints the dependency graph of the library, or of one component, and
+/// fails when it has cycles or dependencies missing from the library.
+pub fn show_graph(component: Option<&str>, format: &str, packages: bool) -> Result<()> {
+    let Some(format) = GraphFormat::from_name(format) else {
+        bail!("Unknown graph format '{}', use ascii, dot or mermaid", format);
+    };
+    let config = get_config().map_err(anyhow::Error::msg)?;
+    let components = library::scan(Path::new(&config.components_dir));
+    let root = component.map(|query| library::find(&components, query)).transpose()?;
+    let graph = DependencyGraph::build(&components, root, packages);
+
+    match format {
+        GraphFormat::Ascii => {
+            let root = root.and_then(|r| graph.nodes.iter().position(|n| n.id == r.id()));
+            print!("{}", graph.to_ascii(root));
+        }
+        GraphFormat::Dot => print!("{}", graph.to_dot()),
+        GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+    }
+
+    let errors = graph.errors();
+    for error in &errors {
+        eprintln!("{} {}", "✗".red().bold(), error);
+    }
+    if !errors.is_empty() {
+        bail!("The dependency graph has {} error(s)", errors.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::test_support::component;
+
+    #[test]
+    fn test_graph_cycles_and_missing() {
+        colored::control::set_override(false);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        component(root, "Dialog", &["Button", "Icon"]);
+        component(root, "Button", &["Icon"]);
+        component(root, "Icon", &[]);
+        component(root, "Menu", &["MenuItem", "Tooltip"]);
+        component(root, "MenuItem", &["Menu"]);
+        let components = library::scan(root);
+
+        let graph = DependencyGraph::build(&components, None, false);
+        assert_eq!(
+            graph.errors(),
+            [
+                "Dependency cycle: vue/tailwind/Menu → vue/tailwind/MenuItem → vue/tailwind/Menu",
+                "vue/tailwind/Menu depends on 'Tooltip', which is not in the library",
+            ]
+        );
+        let tree = graph.to_ascii(None);
+        assert!(tree.contains("vue/tailwind/Dialog\n├── vue/tailwind/Button\n│   └── vue/tailwind/Icon\n└── vue/tailwind/Icon\n"), "{}", tree);
+        assert!(tree.contains("    └── vue/tailwind/Menu ↻ cycle\n"), "{}", tree);
+
+        let dialog = library::find(&components, "Dialog").unwrap();
+        let graph = DependencyGraph::build(&components, Some(dialog), true);
+        assert!(graph.errors().is_empty());
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"vue/tailwind/Dialog\" -> \"vue/tailwind/Button\";"), "{}", dot);
+        assert!(dot.contains("\"vue@^3.0.0\" [shape=ellipse, color=gray];"), "{}", dot);
+        assert!(graph.to_mermaid().starts_with("graph LR\n"));
+
+        // A → B → C → A with a shortcut A → C holds two cycles
+        let cycles = tempfile::tempdir().unwrap();
+        component(cycles.path(), "A", &["B", "C"]);
+        component(cycles.path(), "B", &["C"]);
+        component(cycles.path(), "C", &["A"]);
+        let graph = DependencyGraph::build(&library::scan(cycles.path()), None, false);
+        assert_eq!(
+            graph.errors(),
+            [
+                "Dependency cycle: vue/tailwind/A → vue/tailwind/B → vue/tailwind/C → vue/tailwind/A",
+                "Dependency cycle: vue/tailwind/A → vue/tailwind/C → vue/tailwind/A",
+            ]
+        );
+    }
+}
@@ -1,100 +1,441 @@
-use std::{fs, path::PathBuf};
-use inquire::{Select, Text, Confirm};
-use walkdir::WalkDir;
+use std::{fs, path::{Path, PathBuf}};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use inquire::{MultiSelect, Select, Text};
+use std::collections::BTreeMap;
+use crate::answers;
+use crate::commands::audit;
+use crate::commands::dependencies::{ComponentDependencies, DependencyType};
+use crate::commands::install::{merge_requirements, report_requirements};
 use crate::config::ProjectConfig;
+use crate::library::{self, LibraryComponent};
+use crate::lockfile::{LockedComponent, Lockfile};
+use crate::rename::Rename;
+use crate::source::map_string_literals;
+use crate::taxonomy::Taxonomy;
+use crate::utils::{normalize_path, relative_import_path};
+
+const OVERWRITE_ALL: &str = "Overwrite existing files";
+const SKIP_EXISTING: &str = "Skip existing files";
+const CANCEL: &str = "Cancel import";
+
+/// Extensions tried, in order, for extensionless relative imports
+const SCRIPT_EXTENSIONS: &[&str] = &["vue", "svelte", "tsx", "ts", "jsx", "js"];
 
 fn load_project_config() -> Option<ProjectConfig> {
 	let content = fs::read_to_string(".component-manager.toml").ok()?;
 	toml::from_str(&content).ok()
 }
 
-fn extract_framework_and_style(path: &PathBuf) -> Option<(String, String)> {
-	let components: Vec<_> = path.components()
-		.map(|c| c.as_os_str().to_string_lossy().to_string())
+#[derive(Debug, Default)]
+pub struct ImportOptions {
+	/// Directory to import into, overriding the configured destinations
+	pub to: Option<PathBuf>,
+	/// New name for the imported component; only valid for a single component
+	pub rename: Option<String>,
+}
+
+/// One file to be written by an import.
+struct PlannedFile {
+	/// Index of the component in the selection
+	component: usize,
+	source: PathBuf,
+	destination: PathBuf,
+	/// Rewritten contents when the component is renamed
+	contents: Option<String>,
+}
+
+/// Imports components from the library into the project.
+///
+/// `names` may be bare component names or `framework/style/name`; when empty
+/// the user picks from the components matching the project config. All files
+/// are copied in one transaction: if any copy fails, everything written so far
+/// is rolled back. Each import is recorded in the project lockfile.
+///
+/// Internal dependencies are imported along with the components that need
+/// them, unless the project already has them, and relative imports between
+/// the components are rewritten to match where they end up.
+pub fn import_components(names: &[String], options: &ImportOptions) -> Result<()> {
+	let Some(config) = load_project_config() else {
+		bail!("Could not load `.component-manager.toml`. Make sure you run init first.");
+	};
+
+	let all_components = library::scan(&config.components_dir);
+	let available: Vec<LibraryComponent> = all_components.iter()
+		.filter(|c| config.framework.contains(&c.framework) && config.style.contains(&c.style))
+		.cloned()
+		.collect();
+
+	if available.is_empty() {
+		println!("No components matching your project config.");
+		return Ok(());
+	}
+
+	let selected = if names.is_empty() {
+		answers::multi_select("import.components", MultiSelect::new("Select components to import:", available))?
+	} else {
+		resolve_components(names, &available)?
+	};
+
+	if selected.is_empty() {
+		println!("No components selected.");
+		return Ok(());
+	}
+
+	let rename = match (&options.rename, selected.as_slice()) {
+		(None, _) => None,
+		(Some(new_name), [component]) => Some(Rename::new(&component.name, new_name)),
+		(Some(_), _) => bail!("--as can only be used when importing a single component"),
+	};
+
+	let mut lockfile = Lockfile::load(Path::new("."))?;
+	let explicit_count = selected.len();
+	let (selected, present) = with_internal_dependencies(selected, &all_components, &lockfile)?;
+
+	let targets = resolve_targets(&selected, &config, options.to.as_deref())?;
+	let mut plan = plan_copies(&selected, &targets, config.import.subfolders, rename.as_ref())?;
+	rewrite_relative_imports(&mut plan, &present);
+
+	// Dependencies whose files are all in place already are left alone
+	for (index, component) in selected.iter().enumerate().skip(explicit_count) {
+		let mut files = plan.iter().filter(|file| file.component == index).peekable();
+		if files.peek().is_some() && files.all(|file| file.destination.exists()) {
+			println!("Skipping {}, it is already present", component.name);
+			plan.retain(|file| file.component != index);
+		}
+	}
+
+	let copied: Vec<&LibraryComponent> = selected.iter()
+		.enumerate()
+		.filter(|(index, _)| plan.iter().any(|file| file.component == *index))
+		.map(|(_, component)| component)
 		.collect();
+	if !audit::review_import(&copied, &Taxonomy::load(&config.taxonomy)?, config.audit.threshold)? {
+		println!("Import cancelled.");
+		return Ok(());
+	}
 
-	if components.get(0).map(|c| c.as_str()) != Some("components") {
-		return None;
+	let conflicts: Vec<&PathBuf> = plan.iter().map(|file| &file.destination).filter(|dest| dest.exists()).collect();
+	if !conflicts.is_empty() {
+		println!("The following files already exist:");
+		for dest in &conflicts {
+			println!("  {}", dest.display());
+		}
+		let choice = answers::select(
+			"import.conflicts",
+			Select::new("How should existing files be handled?", vec![OVERWRITE_ALL, SKIP_EXISTING, CANCEL]),
+		)?;
+		match choice {
+			OVERWRITE_ALL => {}
+			SKIP_EXISTING => plan.retain(|file| !file.destination.exists()),
+			_ => {
+				println!("Import cancelled.");
+				return Ok(());
+			}
+		}
 	}
 
-	if components.len() >= 4 {
-		let framework = components.get(1)?.to_string();
-		let style = components.get(2)?.to_string();
-		return Some((framework, style));
+	let locked: Vec<LockedComponent> = selected.iter()
+		.enumerate()
+		.map(|(index, component)| LockedComponent {
+			name: rename.as_ref()
+				.filter(|_| index == 0)
+				.map(|r| r.to.clone())
+				.unwrap_or_else(|| component.name.clone()),
+			source: component.id(),
+			version: component.metadata().map(|m| m.version),
+			files: plan.iter()
+				.filter(|file| file.component == index)
+				.map(|file| file.destination.to_string_lossy().to_string())
+				.collect(),
+		})
+		.filter(|locked| !locked.files.is_empty())
+		.collect();
+
+	copy_all(&plan)?;
+	for file in &plan {
+		println!("✅ Imported to {:?}", file.destination);
 	}
 
-	None
+	for component in locked {
+		lockfile.upsert(component);
+	}
+	lockfile.save(Path::new("."))?;
+
+	print_install_plan(&selected, &config)
 }
 
-pub fn import_component() {
-	let config = match load_project_config() {
-		Some(cfg) => cfg,
-		None => {
-			eprintln!("❌ Could not load `.component-manager.toml`. Make sure you run init first.");
-			return;
+/// Looks up each requested name, failing on unknown or ambiguous names.
+fn resolve_components(names: &[String], available: &[LibraryComponent]) -> Result<Vec<LibraryComponent>> {
+	let mut selected: Vec<LibraryComponent> = Vec::new();
+	for name in names {
+		let component = library::find(available, name)?;
+		if !selected.iter().any(|s| s.id() == component.id()) {
+			selected.push(component.clone());
 		}
-	};
+	}
+	Ok(selected)
+}
+
+/// Picks the destination directory of each selected component.
+///
+/// An explicit `--to` wins. Otherwise components whose kind or tag has a
+/// configured destination go there, and the rest go to the directory
+/// configured for their framework (or `import_dir`). The user is asked for
+/// that directory, with the configured one as default, unless the selected
+/// frameworks are configured with different directories.
+fn resolve_targets(selected: &[LibraryComponent], config: &ProjectConfig, target: Option<&Path>) -> Result<Vec<PathBuf>> {
+	if let Some(dir) = target {
+		return Ok(vec![dir.to_path_buf(); selected.len()]);
+	}
 
-	let mut filtered_paths = vec![];
-	let mut filtered_names = vec![];
+	let kind_dirs: Vec<Option<PathBuf>> = selected.iter()
+		.map(|component| {
+			let metadata = component.metadata()?;
+			config.import.kind_dir(metadata.kind.as_deref(), &metadata.tags).map(Path::to_path_buf)
+		})
+		.collect();
+
+	let mut defaults: Vec<Option<&Path>> = selected.iter()
+		.zip(&kind_dirs)
+		.filter(|(_, kind_dir)| kind_dir.is_none())
+		.map(|(component, _)| config.default_import_dir(&component.framework))
+		.collect();
+	defaults.sort();
+	defaults.dedup();
 
-	for entry in WalkDir::new("components").into_iter().filter_map(|e| e.ok()) {
-		let path = entry.path().to_path_buf();
-		if !path.is_file() {
-			continue;
+	// The directory asked for replaces a single shared default, or fills in
+	// for the frameworks without one.
+	let shared_default = match defaults.as_slice() {
+		[Some(default)] => Some(*default),
+		_ => None,
+	};
+	let base_dir = if shared_default.is_some() || defaults.contains(&None) {
+		let default = shared_default.map(|d| d.to_string_lossy().to_string());
+		let mut prompt = Text::new("Target project directory:");
+		if let Some(default) = &default {
+			prompt = prompt.with_default(default);
 		}
+		Some(PathBuf::from(answers::text("import.target_dir", prompt)?))
+	} else {
+		None
+	};
 
-		if let Some((framework, style)) = extract_framework_and_style(&path) {
-			if config.framework.contains(&framework) && config.style.contains(&style) {
-				if let Some(fname) = path.file_name().and_then(|n| n.to_str()) {
-					filtered_paths.push(path.clone());
-					filtered_names.push(fname.to_string());
+	Ok(selected.iter()
+		.zip(kind_dirs)
+		.map(|(component, kind_dir)| {
+			let framework_dir = config.default_import_dir(&component.framework)
+				.filter(|_| shared_default.is_none())
+				.map(Path::to_path_buf);
+			kind_dir
+				.or(framework_dir)
+				.or_else(|| base_dir.clone())
+				.unwrap_or_default()
+		})
+		.collect())
+}
+
+/// Maps every source file of the selected components to its destination.
+/// A rename applies to the first component, the one named on the command line.
+fn plan_copies(
+	selected: &[LibraryComponent],
+	targets: &[PathBuf],
+	subfolders: bool,
+	rename: Option<&Rename>,
+) -> Result<Vec<PlannedFile>> {
+	let mut plan: Vec<PlannedFile> = Vec::new();
+	for (index, (component, target_dir)) in selected.iter().zip(targets).enumerate() {
+		let rename = rename.filter(|_| index == 0);
+		let name = rename.map(|r| r.to.as_str()).unwrap_or(&component.name);
+		let component_dir = if subfolders { target_dir.join(name) } else { target_dir.clone() };
+		for file in &component.files {
+			let relative = file.strip_prefix(&component.path).ok()
+				.filter(|r| !r.as_os_str().is_empty())
+				.unwrap_or_else(|| Path::new(file.file_name().unwrap()));
+			let mut destination = component_dir.join(relative);
+			let mut contents = None;
+			if let Some(rename) = rename {
+				let file_name = destination.file_name().unwrap().to_string_lossy().to_string();
+				destination.set_file_name(rename.file_name(&file_name));
+				let ext = file.extension().and_then(|e| e.to_str()).unwrap_or_default();
+				if Rename::rewrites(ext) {
+					let source = fs::read_to_string(file)
+						.with_context(|| format!("Failed to read {}", file.display()))?;
+					contents = Some(rename.source(ext, &source));
 				}
 			}
+			if let Some(other) = plan.iter().find(|planned| planned.destination == destination) {
+				bail!(
+					"Both {} and {} would be imported to {}",
+					other.source.display(),
+					file.display(),
+					destination.display()
+				);
+			}
+			plan.push(PlannedFile { component: index, source: file.clone(), destination, contents });
 		}
 	}
+	Ok(plan)
+}
 
-	if filtered_names.is_empty() {
-		println!("No components matching your project config.");
-		return;
-	}
-
-	let selected_name = Select::new("Select a component to import:", filtered_names)
-		.prompt()
-		.expect("Failed to select component");
+/// An internal dependency the project already has, with its lockfile entry
+type PresentDependency<'a> = (LibraryComponent, &'a LockedComponent);
 
-	let source = filtered_paths.iter()
-		.find(|p| p.file_name().and_then(|n| n.to_str()) == Some(&selected_name))
-		.expect("Selected file not found");
+/// Adds the internal dependencies of the selected components, transitively.
+///
+/// Dependencies recorded in the lockfile are not imported again; they are
+/// returned alongside their lockfile entry so imports of them can be rewritten.
+fn with_internal_dependencies<'a>(
+	mut selected: Vec<LibraryComponent>,
+	all_components: &[LibraryComponent],
+	lockfile: &'a Lockfile,
+) -> Result<(Vec<LibraryComponent>, Vec<PresentDependency<'a>>)> {
+	let locked = |component: &LibraryComponent| lockfile.components.iter().find(|locked| locked.source == component.id());
+	let roots: Vec<&LibraryComponent> = all_components.iter().filter(|c| selected.iter().any(|s| s.id() == c.id())).collect();
+	let walk = library::walk_dependencies(all_components, roots.iter().copied(), |component| locked(component).is_none());
+	// Like `install`, a missing dependency is reported but does not stop the import
+	for edge in walk.missing() {
+		println!("{} {} depends on '{}', which is not in the library", "⚠".yellow().bold(), edge.dependent.name, edge.reference);
+	}
 
-	let target_dir = Text::new("Target project directory:")
-		.prompt()
-		.expect("Failed to read target path");
+	let mut present: Vec<PresentDependency> = Vec::new();
+	for component in walk.components.iter().skip(roots.len()) {
+		match locked(component) {
+			Some(locked) => present.push(((*component).clone(), locked)),
+			None => {
+				let required_by = walk.edges.iter()
+					.find(|edge| edge.dependency.is_some_and(|d| d.id() == component.id()))
+					.map_or("", |edge| edge.dependent.name.as_str());
+				println!("Also importing {} (required by {})", component.name, required_by);
+				selected.push((*component).clone());
+			}
+		}
+	}
+	Ok((selected, present))
+}
 
-	let destination = PathBuf::from(&target_dir).join(source.file_name().unwrap());
+/// Points relative imports between library components at the files' new
+/// locations in the project.
+fn rewrite_relative_imports(plan: &mut [PlannedFile], present: &[PresentDependency]) {
+	let mut locations: BTreeMap<PathBuf, PathBuf> = plan.iter()
+		.map(|file| (normalize_path(&file.source), file.destination.clone()))
+		.collect();
+	for (component, locked) in present {
+		let rename = Rename::new(&component.name, &locked.name);
+		for file in &component.files {
+			let file_name = rename.file_name(&file.file_name().unwrap().to_string_lossy());
+			if let Some(project_file) = locked.files.iter().find(|f| f.ends_with(&file_name)) {
+				locations.insert(normalize_path(file), PathBuf::from(project_file));
+			}
+		}
+	}
 
-	if destination.exists() {
-		let overwrite = Confirm::new(&format!(
-			"File {:?} already exists. Overwrite?", destination.file_name().unwrap()
-		))
-		.with_default(false)
-		.prompt();
+	for file in plan.iter_mut() {
+		let ext = file.source.extension().and_then(|e| e.to_str()).unwrap_or_default().to_string();
+		let contents = match &file.contents {
+			Some(contents) => contents.clone(),
+			None => match fs::read_to_string(&file.source) {
+				Ok(contents) => contents,
+				// Binary files have no imports to rewrite
+				Err(_) => continue,
+			},
+		};
+		let source_dir = file.source.parent().unwrap_or(Path::new(""));
+		let destination_dir = file.destination.parent().unwrap_or(Path::new("")).to_path_buf();
+		let rewritten = map_string_literals(&ext, &contents, |specifier| {
+			if !specifier.starts_with('.') {
+				return None;
+			}
+			let resolved = normalize_path(&source_dir.join(specifier));
+			// Extensionless specifiers resolve like a bundler would
+			let (target, had_extension) = match locations.get(&resolved) {
+				Some(target) => (target, true),
+				None => SCRIPT_EXTENSIONS.iter()
+					.find_map(|ext| locations.get(&resolved.with_extension(ext)))
+					.map(|target| (target, false))?,
+			};
+			let mut path = relative_import_path(&destination_dir, target);
+			if !had_extension && let Some(ext) = target.extension() {
+				path.truncate(path.len() - ext.len() - 1);
+			}
+			(path != specifier).then_some(path)
+		});
+		if rewritten != contents {
+			file.contents = Some(rewritten);
+		}
+	}
+}
 
-		match overwrite {
-			Ok(true) => { /* proceed */ }
-			Ok(false) => {
-				println!("Import cancelled.");
-				return;
+/// Writes all files, restoring the previous state if any write fails.
+fn copy_all(plan: &[PlannedFile]) -> Result<()> {
+	let mut created_dirs: Vec<PathBuf> = Vec::new();
+	let mut written: Vec<(&Path, Option<Vec<u8>>)> = Vec::new();
+	for file in plan {
+		let dest = file.destination.as_path();
+		let result = create_parent_dirs(dest, &mut created_dirs).and_then(|_| {
+			let previous = fs::read(dest).ok();
+			let result = match &file.contents {
+				Some(contents) => fs::write(dest, contents),
+				None => fs::copy(&file.source, dest).map(|_| ()),
+			};
+			written.push((dest, previous));
+			result
+		});
+		if let Err(e) = result {
+			for (path, previous) in written.iter().rev() {
+				let _ = match previous {
+					Some(content) => fs::write(path, content),
+					None => fs::remove_file(path),
+				};
 			}
-			Err(_) => {
-				eprintln!("Prompt failed, aborting.");
-				return;
+			for dir in created_dirs.iter().rev() {
+				let _ = fs::remove_dir(dir);
 			}
+			return Err(e).with_context(|| format!("Error importing {}, no files were changed", file.source.display()));
 		}
 	}
+	Ok(())
+}
 
-	match fs::copy(&source, &destination) {
-		Ok(_) => println!("✅ Imported to {:?}", destination),
-		Err(e) => eprintln!("❌ Error importing component: {}", e),
+/// Creates the missing parent directories of `path`, recording each one created.
+fn create_parent_dirs(path: &Path, created: &mut Vec<PathBuf>) -> std::io::Result<()> {
+	let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+		return Ok(());
+	};
+	let mut missing: Vec<&Path> = parent.ancestors()
+		.take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+		.collect();
+	missing.reverse();
+	for dir in missing {
+		fs::create_dir(dir)?;
+		created.push(dir.to_path_buf());
+	}
+	Ok(())
+}
+
+/// Prints one combined install plan for the dependencies of all imported components.
+fn print_install_plan(selected: &[LibraryComponent], config: &ProjectConfig) -> Result<()> {
+	let mut dependencies = ComponentDependencies::new();
+	for component in selected {
+		// Internal dependencies were imported along with the components
+		if let Some(mut metadata) = component.metadata() {
+			metadata.dependencies.dependencies.retain(|d| !matches!(d, DependencyType::Internal(_)));
+			merge_requirements(&mut dependencies, &metadata.dependencies, &component.id());
+		}
+	}
+
+	if dependencies.dependencies.is_empty() {
+		return Ok(());
+	}
+	println!();
+	let commands = report_requirements(&dependencies, config, Path::new("."), false)?.commands;
+	if commands.is_empty() {
+		println!("The project already has every dependency of the imported components");
+		return Ok(());
+	}
+	println!("Install the missing dependencies of the imported components with:");
+	for cmd in &commands {
+		println!("  {}", cmd);
 	}
+	Ok(())
 }
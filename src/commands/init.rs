@@ -1,17 +1,69 @@
-use inquire::MultiSelect;
+use anyhow::{bail, Context, Result};
+use inquire::{Confirm, MultiSelect};
 use std::fs;
-use std::path::PathBuf;
-use crate::config::ProjectConfig;
-use crate::utils::{SUPPORTED_FRAMEWORKS, SUPPORTED_STYLES, SUPPORTED_LANGUAGES};
+use std::path::{Path, PathBuf};
+use crate::answers;
+use crate::config::{AuditConfig, GlobalConfig, ImportConfig, LintConfig, Preset, ProjectConfig, SecretsConfig, TaxonomyConfig, CONFIG_FILE};
+use crate::stack::{detect_stack, Detected, StackDetection};
+use crate::taxonomy::{Entry, Taxonomy};
+use crate::utils::line_diff;
+use toml_edit::{value, Array, DocumentMut};
 
-fn prompt_with_validation(prompt_text: &str, options: Vec<String>) -> Vec<String> {
+/// Name, description, framework, style and language of a built-in preset.
+type BuiltinPreset = (&'static str, &'static str, &'static [&'static str], &'static [&'static str], &'static [&'static str]);
+
+/// Presets available to `init --preset`. Presets in the global config take
+/// precedence.
+const BUILTIN_PRESETS: &[BuiltinPreset] = &[
+	("nuxt-tailwind-ts", "Nuxt with Tailwind CSS and TypeScript", &["nuxt", "vue"], &["tailwind"], &["typescript"]),
+	("vue-tailwind-ts", "Vue with Tailwind CSS and TypeScript", &["vue"], &["tailwind"], &["typescript"]),
+	("vue-bootstrap-js", "Vue with Bootstrap and JavaScript", &["vue"], &["bootstrap"], &["javascript"]),
+	("react-tailwind-ts", "React with Tailwind CSS and TypeScript", &["react"], &["tailwind"], &["typescript"]),
+	("next-tailwind-ts", "Next.js with Tailwind CSS and TypeScript", &["nextjs", "react"], &["tailwind"], &["typescript"]),
+	("svelte-tailwind-ts", "Svelte with Tailwind CSS and TypeScript", &["svelte"], &["tailwind"], &["typescript"]),
+	("angular-scss-ts", "Angular with SCSS and TypeScript", &["angular"], &["scss"], &["typescript"]),
+	("laravel-tailwind-php", "Laravel with Tailwind CSS", &["laravel", "php"], &["tailwind"], &["php"]),
+	("django-bootstrap-py", "Django with Bootstrap", &["django"], &["bootstrap"], &["python"]),
+];
+
+const STARTER_README: &str = "# Components
+
+Components are stored as `<framework>/<style>/<Name>/`, each with a
+`component.toml` describing its framework, style, language and dependencies.
+
+- `component-manager export <glob>` adds components from a project
+- `component-manager import <name>...` copies components into a project
+- `component-manager show` lists what is available
+- `component-manager lint` and `component-manager fsck` keep the library consistent
+";
+
+/// Options for `init`.
+pub struct InitOptions {
+	/// Use the detected stack without prompting
+	pub auto: bool,
+	/// Name of a built-in or user-defined preset
+	pub preset: Option<String>,
+	/// Write without asking for confirmation
+	pub yes: bool,
+	/// Create a starter README in the components directory
+	pub readme: bool,
+}
+
+/// Asks for any number of `options`, listed by name, and returns the ids
+/// picked. The `selected` ids are pre-selected.
+fn prompt_with_validation(id: &str, prompt_text: &str, options: &[Entry], selected: &[String]) -> Vec<String> {
+	let defaults: Vec<usize> = options.iter()
+		.enumerate()
+		.filter(|(_, option)| selected.contains(&option.id))
+		.map(|(i, _)| i)
+		.collect();
 	loop {
-		let ans = MultiSelect::new(prompt_text, options.clone())
-			.with_vim_mode(true)
-			.prompt();
+		let ans = answers::multi_select(id, MultiSelect::new(prompt_text, options.to_vec())
+			.with_default(&defaults)
+			.with_vim_mode(true));
 
 		match ans {
-			Ok(selection) if !selection.is_empty() => return selection,
+			Ok(selection) if !selection.is_empty() => return selection.into_iter().map(|entry| entry.id).collect(),
 			Ok(_) => println!("❌ You must select at least one option. Please try again."),
 			Err(e) => {
 				eprintln!("❌ Prompt failed: {}", e);
@@ -21,39 +73,244 @@ fn prompt_with_validation(prompt_text: &str, options: Vec<String>) -> Vec<String
 	}
 }
 
+/// Prints what was detected for one category and why.
+fn print_detected(category: &str, detected: &[Detected]) {
+	if detected.is_empty() {
+		println!("  {}: nothing detected", category);
+	}
+	for d in detected {
+		println!("  {}: {} ({})", category, d.value, d.reason);
+	}
+}
+
+/// The detected values, or `none` when nothing was found.
+fn detected_or_none(detected: &[Detected]) -> Vec<String> {
+	match StackDetection::values(detected) {
+		values if values.is_empty() => vec!["none".to_string()],
+		values => values,
+	}
+}
+
+/// Looks a preset up in the global config, then among the built-in ones.
+fn find_preset(name: &str) -> Result<Preset> {
+	let mut global = GlobalConfig::load()?;
+	if let Some(preset) = global.presets.remove(name) {
+		return Ok(preset);
+	}
+	if let Some((_, description, framework, style, language)) = BUILTIN_PRESETS.iter().find(|(n, ..)| *n == name) {
+		let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+		return Ok(Preset {
+			description: Some(description.to_string()),
+			framework: strings(framework),
+			style: strings(style),
+			language: strings(language),
+			components_dir: None,
+		});
+	}
+	let mut available: Vec<&str> = BUILTIN_PRESETS.iter().map(|(n, ..)| *n).collect();
+	available.extend(global.presets.keys().map(String::as_str));
+	available.sort_unstable();
+	available.dedup();
+	bail!("Unknown preset '{}'. Available presets: {}", name, available.join(", "));
+}
+
+/// Sets the stack keys of an existing config, keeping its comments,
+/// formatting and other keys as they are. New keys go after the last
+/// top-level key.
+fn merge_into_config(
+	content: &str,
+	framework: &[String],
+	style: &[String],
+	language: &[String],
+	components_dir: Option<&Path>,
+) -> Result<String> {
+	let mut document: DocumentMut = content.parse()
+		.with_context(|| format!("{} is not valid, fix or remove it first", CONFIG_FILE))?;
+	for (key, values) in [("framework", framework), ("style", style), ("language", language)] {
+		document[key] = value(values.iter().map(String::as_str).collect::<Array>());
+	}
+	if let Some(dir) = components_dir {
+		document["components_dir"] = value(dir.display().to_string());
+	}
+	Ok(document.to_string())
+}
+
 /// Initializes a `.component-manager.toml` file with selected framework and styling options.
-pub fn init_config() {
-	let frameworks = SUPPORTED_FRAMEWORKS
-		.iter()
-		.map(|&f| f.to_string())
-		.collect::<Vec<String>>();
-
-	let styles = SUPPORTED_STYLES
-		.iter()
-		.map(|&s| s.to_string())
-		.collect::<Vec<String>>();
-
-	let languages = SUPPORTED_LANGUAGES
-		.iter()
-		.map(|&l| l.to_string())
-		.collect::<Vec<String>>();
-
-	let selected_framework = prompt_with_validation("Select a framework:", frameworks);
-	let selected_style = prompt_with_validation("Select a styling library:", styles);
-	let selected_language = prompt_with_validation("Select a language:", languages);
-
-	let config = ProjectConfig {
-		framework: selected_framework,
-		style: selected_style,
-		language: selected_language,
-		components_dir: PathBuf::from("./components"),
+///
+/// The values come from a preset, or from prompts pre-selected with the
+/// existing config or what the project's manifests reveal; with `auto` the
+/// detected values are used without prompting, and an existing config keeps
+/// the values of the categories nothing was detected for. An existing config
+/// is updated in place, keeping its other keys, after showing the changes.
+pub fn init_config(options: &InitOptions) -> Result<()> {
+	let existing = match fs::read_to_string(CONFIG_FILE) {
+		Ok(content) => Some(content),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+		Err(e) => return Err(e).with_context(|| format!("Failed to read {}", CONFIG_FILE)),
+	};
+	let current: Option<ProjectConfig> = existing.as_deref()
+		.map(toml::from_str)
+		.transpose()
+		.with_context(|| format!("{} is not valid, fix or remove it first", CONFIG_FILE))?;
+
+	// Entries from the global and project config are offered and detected too
+	let known = Taxonomy::load(current.as_ref().map(|c| &c.taxonomy).unwrap_or(&TaxonomyConfig::default()))?;
+
+	let (selected_framework, selected_style, selected_language, components_dir) = if let Some(name) = &options.preset {
+		let preset = find_preset(name)?;
+		match &preset.description {
+			Some(description) => println!("Using preset {}: {}", name, description),
+			None => println!("Using preset {}", name),
+		}
+		(preset.framework, preset.style, preset.language, preset.components_dir)
+	} else {
+		let detection = detect_stack(Path::new("."), &known);
+		if detection.is_empty() {
+			println!("ℹ️  Could not detect the project stack, nothing is pre-selected.");
+		} else {
+			println!("Detected from the project:");
+			print_detected("framework", &detection.framework);
+			print_detected("style", &detection.style);
+			print_detected("language", &detection.language);
+		}
+
+		if options.auto {
+			// Categories nothing was detected for keep their current values
+			let pick = |detected: &[Detected], values: fn(&ProjectConfig) -> &Vec<String>| match &current {
+				Some(config) if detected.is_empty() => values(config).clone(),
+				_ => detected_or_none(detected),
+			};
+			(
+				pick(&detection.framework, |c| &c.framework),
+				pick(&detection.style, |c| &c.style),
+				pick(&detection.language, |c| &c.language),
+				None,
+			)
+		} else {
+			// Re-running init starts from the current choices
+			let (framework, style, language) = match &current {
+				Some(config) => (config.framework.clone(), config.style.clone(), config.language.clone()),
+				None => (
+					StackDetection::values(&detection.framework),
+					StackDetection::values(&detection.style),
+					StackDetection::values(&detection.language),
+				),
+			};
+			(
+				prompt_with_validation("init.framework", "Select a framework:", &known.frameworks, &framework),
+				prompt_with_validation("init.style", "Select a styling library:", &known.styles, &style),
+				prompt_with_validation("init.language", "Select a language:", &known.languages, &language),
+				None,
+			)
+		}
 	};
 
-	let toml_string = toml::to_string(&config)
-		.expect("Failed to serialize config");
+	let content = match &existing {
+		Some(content) => merge_into_config(
+			content,
+			&selected_framework,
+			&selected_style,
+			&selected_language,
+			components_dir.as_deref(),
+		)?,
+		None => {
+			let config = ProjectConfig {
+				framework: selected_framework,
+				style: selected_style,
+				language: selected_language,
+				components_dir: components_dir.unwrap_or_else(|| PathBuf::from("./components")),
+				import_dir: None,
+				import: ImportConfig::default(),
+				package_manager: None,
+				projects: Vec::new(),
+				registry: None,
+				lint: LintConfig::default(),
+				audit: AuditConfig::default(),
+				secrets: SecretsConfig::default(),
+				taxonomy: TaxonomyConfig::default(),
+			};
+			toml::to_string(&config).context("Failed to serialize config")?
+		}
+	};
+	let config: ProjectConfig = toml::from_str(&content)
+		.with_context(|| format!("Failed to update {}", CONFIG_FILE))?;
+
+	match &existing {
+		Some(old) if *old == content => println!("✅ `{}` is already up to date.", CONFIG_FILE),
+		Some(old) => {
+			println!("Changes to `{}`:", CONFIG_FILE);
+			for (mark, line) in line_diff(old, &content) {
+				if mark != ' ' {
+					println!("{} {}", mark, line);
+				}
+			}
+			let write = options.yes || answers::confirm(
+				"init.write",
+				Confirm::new(&format!("Update `{}`?", CONFIG_FILE)).with_default(true),
+			)
+			.context("Failed to read confirmation, pass --yes to update without asking")?;
+			if !write {
+				println!("Left `{}` unchanged.", CONFIG_FILE);
+				return Ok(());
+			}
+			fs::write(CONFIG_FILE, &content).with_context(|| format!("Failed to write {}", CONFIG_FILE))?;
+			println!("✅ Updated `{}`.", CONFIG_FILE);
+		}
+		None => {
+			fs::write(CONFIG_FILE, &content).with_context(|| format!("Failed to write {}", CONFIG_FILE))?;
+			println!("✅ Created `{}` in current directory.", CONFIG_FILE);
+		}
+	}
+
+	if !config.components_dir.exists() {
+		fs::create_dir_all(&config.components_dir)
+			.with_context(|| format!("Failed to create {}", config.components_dir.display()))?;
+		println!("✅ Created components directory `{}`.", config.components_dir.display());
+	}
+
+	let readme = config.components_dir.join("README.md");
+	if !readme.exists() {
+		let create = options.readme || (!options.yes && !options.auto && answers::confirm(
+			"init.readme",
+			Confirm::new("Add a starter README to the components directory?").with_default(false),
+		)?);
+		if create {
+			fs::write(&readme, STARTER_README).with_context(|| format!("Failed to write {}", readme.display()))?;
+			println!("✅ Created `{}`.", readme.display());
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_merge_into_config() {
+		let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+		let content = "# Project setup\nframework = [\n  \"vue\",\n]\nregistry = \"https://npm.example.com\"\n\n[lint]\nmax_lines = 200\n";
+		let updated = merge_into_config(
+			content,
+			&strings(&["react"]),
+			&strings(&["tailwind"]),
+			&strings(&["typescript"]),
+			Some(Path::new("ui")),
+		)
+		.unwrap();
+		assert_eq!(
+			updated,
+			"# Project setup\nframework = [\"react\"]\nregistry = \"https://npm.example.com\"\nstyle = [\"tailwind\"]\n\
+			 language = [\"typescript\"]\ncomponents_dir = \"ui\"\n\n[lint]\nmax_lines = 200\n"
+		);
 
-	match fs::write(".component-manager.toml", toml_string) {
-		Ok(_) => println!("✅ Created `.component-manager.toml` in current directory."),
-		Err(e) => eprintln!("❌ Failed to write config: {}", e),
+		// Keys of the same name inside tables are left alone
+		let content = "registry = \"https://npm.example.com/[team]\"\n[lint]\nframework = 1\n";
+		let updated = merge_into_config(content, &strings(&["vue"]), &strings(&["scss"]), &strings(&["javascript"]), None).unwrap();
+		assert_eq!(
+			updated,
+			"registry = \"https://npm.example.com/[team]\"\nframework = [\"vue\"]\nstyle = [\"scss\"]\nlanguage = [\"javascript\"]\n[lint]\nframework = 1\n"
+		);
 	}
 }
@@ -1,40 +1,73 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use inquire::{Confirm, MultiSelect};
 
-use crate::config::get_config;
-use crate::commands::dependencies::ComponentDependencies;
+use crate::answers;
+use crate::config::{get_config, ProjectConfig};
+use crate::ecosystem::Ecosystem;
+use crate::library::{self, LibraryComponent};
+use crate::commands::dependencies::{intersect_requirements, split_npm, ComponentDependencies, DependencyKind, DependencyType};
+use crate::npm::{merge_into_manifest, ManifestChange, ManifestEntry, ProjectPackages, RequirementCheck, RequirementStatus};
+use crate::package_manager::{InstallCommand, PackageManager, Workspace};
 
-pub fn install_dependencies(component_name: Option<&str>) -> Result<()> {
-    let project_config = get_config().unwrap();
-    let components_dir = Path::new(&project_config.components_dir);
-    
-    if let Some(name) = component_name {
-        // Install dependencies for a specific component
-        let component_path = components_dir.join(name);
-        if !component_path.exists() {
-            anyhow::bail!("Component '{}' not found", name);
-        }
-        
-        let deps = load_component_dependencies(&component_path)?;
-        install_dependencies_for(&deps, components_dir)
-    } else {
-        // Install dependencies for all components
-        let mut all_deps = ComponentDependencies::new();
-        
-        // Load all components' dependencies
-        if let Ok(entries) = std::fs::read_dir(components_dir) {
-            for entry in entries.flatten() {
-                if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
-                    if let Ok(deps) = load_component_dependencies(&entry.path()) {
-                        all_deps.dependencies.extend(deps.dependencies);
-                    }
-                }
+/// How `install` runs the install commands.
+#[derive(Debug, Default, Clone)]
+pub struct InstallOptions {
+    /// Run the commands without asking for confirmation
+    pub yes: bool,
+    /// Only print the commands
+    pub dry_run: bool,
+    /// Add the dependencies to `package.json` instead of running the package manager
+    pub write_manifest: bool,
+}
+
+/// What `report_requirements` found missing, as commands and as entries for
+/// the project's `package.json`.
+#[derive(Debug, Default)]
+pub struct InstallPlan {
+    pub commands: Vec<InstallCommand>,
+    /// npm packages to declare, with the range narrowed to what both the
+    /// project and the components accept
+    pub manifest: Vec<ManifestEntry>,
+}
+
+/// Installs the dependencies of one component, given by name or as
+/// `framework/style/name`, together with those of the components it depends
+/// on, or of every component in the library.
+pub fn install_dependencies(component_name: Option<&str>, options: &InstallOptions) -> Result<()> {
+    let project_config = get_config().map_err(anyhow::Error::msg)?;
+    let components = library::scan(Path::new(&project_config.components_dir));
+
+    let selected: Vec<&LibraryComponent> = match component_name {
+        Some(name) => {
+            let walk = library::walk_dependencies(&components, [library::find(&components, name)?], |_| true);
+            // A missing component is reported, its packages cannot be installed anyway
+            for edge in walk.missing() {
+                println!(
+                    "{} {} depends on '{}', which is not in the library",
+                    "⚠".yellow().bold(),
+                    edge.dependent.name,
+                    edge.reference
+                );
             }
+            walk.components
+        }
+        None => components.iter().collect(),
+    };
+
+    let mut all_deps = ComponentDependencies::new();
+    for component in selected {
+        if !component.metadata_path().exists() {
+            continue;
+        }
+        match load_component_dependencies(&component.path) {
+            Ok(deps) => merge_requirements(&mut all_deps, &deps, &component.name),
+            Err(e) => println!("{} Skipping {}: {:#}", "⚠".yellow().bold(), component, e),
         }
-        
-        install_dependencies_for(&all_deps, components_dir)
     }
+    install_dependencies_for(&all_deps, &project_config, options)
 }
 
 fn load_component_dependencies(component_path: &Path) -> Result<ComponentDependencies> {
@@ -52,26 +85,335 @@ fn load_component_dependencies(component_path: &Path) -> Result<ComponentDepende
     Ok(metadata.dependencies)
 }
 
-fn install_dependencies_for(deps: &ComponentDependencies, base_path: &Path) -> Result<()> {
-    let commands = deps.generate_install_commands(base_path);
-    
-    if commands.is_empty() {
+fn install_dependencies_for(deps: &ComponentDependencies, project_config: &ProjectConfig, options: &InstallOptions) -> Result<()> {
+    if deps.dependencies.is_empty() {
         println!("{} No dependencies to install", "✓".green().bold());
         return Ok(());
     }
-    
+
+    let internal: Vec<&str> = deps.dependencies.iter()
+        .filter_map(|d| match d {
+            DependencyType::Internal(reference) => Some(reference.as_str()),
+            _ => None,
+        })
+        .collect();
+    if !internal.is_empty() {
+        println!("{} Also requires the components {}", "ℹ".blue().bold(), internal.join(", "));
+    }
+
+    let ask_optional = !options.yes && !options.dry_run;
+    let plan = report_requirements(deps, project_config, Path::new("."), ask_optional)?;
+    if options.write_manifest {
+        return write_manifest(&plan, Path::new("."), options.dry_run);
+    }
+    let commands = plan.commands;
+    if commands.is_empty() {
+        println!("{} All dependencies are already satisfied", "✓".green().bold());
+        return Ok(());
+    }
+
+    if options.dry_run {
+        println!("{} The following commands would be executed:", "ℹ".blue().bold());
+        for cmd in &commands {
+            println!("  {}", cmd);
+        }
+        println!("\n{} Run the above commands to install dependencies", "ℹ".blue().bold());
+        return Ok(());
+    }
+
     println!("{} The following commands will be executed:", "ℹ".blue().bold());
     for cmd in &commands {
         println!("  {}", cmd);
     }
-    
-    // TODO: Actually execute the commands after user confirmation
-    // For now, just print what would be done
-    println!("\n{} Run the above commands to install dependencies", "ℹ".blue().bold());
-    
+    if !options.yes {
+        let proceed = answers::confirm("install.confirm", Confirm::new("Run these commands?").with_default(false))
+            .context("Failed to read confirmation, pass --yes to install without asking")?;
+        if !proceed {
+            println!("Installation cancelled");
+            return Ok(());
+        }
+    }
+
+    run_commands(&commands)
+}
+
+/// Merges the npm packages of `plan` into the project's `package.json` and
+/// reports each entry added or changed. Other ecosystems are only listed.
+fn write_manifest(plan: &InstallPlan, project_dir: &Path, dry_run: bool) -> Result<()> {
+    let path = project_dir.join("package.json");
+    let content = if path.exists() {
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        "{\n}\n".to_string()
+    };
+    let (merged, changes) = merge_into_manifest(&content, &plan.manifest)
+        .with_context(|| format!("Failed to update {}", path.display()))?;
+
+    if changes.is_empty() {
+        println!("{} package.json already declares every dependency", "✓".green().bold());
+    } else {
+        let verb = if dry_run { "would be" } else { "were" };
+        println!("{} These package.json entries {} written:", "ℹ".blue().bold(), verb);
+        for ManifestChange { entry, previous } in &changes {
+            match previous {
+                Some(previous) => println!("  ~ {}.{}: {} → {}", entry.section, entry.name, previous, entry.range),
+                None => println!("  + {}.{}: {}", entry.section, entry.name, entry.range),
+            }
+        }
+        if !dry_run {
+            std::fs::write(&path, merged).with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("{} Updated {}, run your package manager to install", "✓".green().bold(), path.display());
+        }
+    }
+
+    let others: Vec<&InstallCommand> = plan.commands.iter()
+        .filter(|c| PackageManager::from_name(&c.program).is_none())
+        .collect();
+    if !others.is_empty() {
+        println!("{} package.json only covers npm packages, install the others with:", "ℹ".blue().bold());
+        for command in others {
+            println!("  {}", command);
+        }
+    }
     Ok(())
 }
 
+/// Runs the commands one after another, streaming their output, and stops at
+/// the first one that fails.
+fn run_commands(commands: &[InstallCommand]) -> Result<()> {
+    let mut results: Vec<(&InstallCommand, String)> = Vec::new();
+    let mut failure = None;
+    for command in commands {
+        println!("\n{} {}", "▶".blue().bold(), command);
+        let outcome = duct::cmd(&command.program, &command.args).unchecked().run();
+        let status = match outcome {
+            Ok(output) if output.status.success() => "exit 0".to_string(),
+            Ok(output) => {
+                let code = output.status.code().map_or("signal".to_string(), |c| format!("exit {}", c));
+                failure = Some(format!("`{}` failed with {}", command, code));
+                code
+            }
+            Err(e) => {
+                failure = Some(format!("Could not run `{}`: {}", command, e));
+                "not started".to_string()
+            }
+        };
+        results.push((command, status));
+        if failure.is_some() {
+            break;
+        }
+    }
+
+    println!("\nSummary:");
+    for (command, status) in &results {
+        let mark = if status == "exit 0" { "✓".green().bold() } else { "✗".red().bold() };
+        println!("  {} {} ({})", mark, command, status);
+    }
+    for command in &commands[results.len()..] {
+        println!("  {} {} (skipped)", "-".dimmed(), command);
+    }
+
+    match failure {
+        Some(message) => bail!(message),
+        None => {
+            println!("{} Dependencies installed", "✓".green().bold());
+            Ok(())
+        }
+    }
+}
+
+/// Adds the dependencies of `component` to `all`, narrowing shared npm
+/// requirements to a range every component accepts and warning about the
+/// ones that cannot be reconciled.
+pub fn merge_requirements(all: &mut ComponentDependencies, deps: &ComponentDependencies, component: &str) {
+    for (kept, rejected) in all.merge(deps) {
+        if let (Some((kept, _)), Some((rejected, _))) = (kept.npm_requirement(), rejected.npm_requirement()) {
+            println!(
+                "{} {} requires {}, which no version allowed by {} satisfies",
+                "⚠".yellow().bold(),
+                component,
+                rejected,
+                kept
+            );
+        }
+    }
+}
+
+/// A requirement of the components and how the project meets it.
+struct Requirement {
+    /// `None` for npm packages
+    ecosystem: Option<Ecosystem>,
+    kind: DependencyKind,
+    reason: Option<String>,
+    check: RequirementCheck,
+}
+
+/// Prints how each requirement compares with the project in `project_dir`
+/// and returns the commands needed to install what is missing or out of
+/// range. Packages of other ecosystems are checked against the project's
+/// own manifest, e.g. `requirements.txt` or `go.mod`. Peer dependencies are
+/// only reported; optional ones are offered when `ask_optional` is set and
+/// listed otherwise.
+pub fn report_requirements(
+    deps: &ComponentDependencies,
+    project_config: &ProjectConfig,
+    project_dir: &Path,
+    ask_optional: bool,
+) -> Result<InstallPlan> {
+    let packages = ProjectPackages::load(project_dir);
+    let mut requirements: Vec<Requirement> = deps.dependencies.iter()
+        .filter_map(|d| {
+            let (requirement, kind) = d.npm_requirement()?;
+            let reason = match d {
+                DependencyType::Package(package) => package.reason.clone(),
+                _ => None,
+            };
+            Some(Requirement { ecosystem: None, kind, reason, check: packages.check(&requirement) })
+        })
+        .collect();
+    let mut manifests = BTreeMap::new();
+    for (ecosystem, requirement) in deps.dependencies.iter().filter_map(DependencyType::ecosystem_requirement) {
+        let declared = manifests.entry(ecosystem).or_insert_with(|| ecosystem.declared(project_dir));
+        requirements.push(Requirement {
+            ecosystem: Some(ecosystem),
+            kind: DependencyKind::Runtime,
+            reason: None,
+            check: ecosystem.check(declared, requirement),
+        });
+    }
+
+    if !requirements.is_empty() {
+        print_requirement_table(&requirements);
+    }
+
+    let mut needed = ComponentDependencies::new();
+    let mut dev = BTreeSet::new();
+    let mut peers = Vec::new();
+    let mut optional = Vec::new();
+    for Requirement { ecosystem, kind, reason, check } in &requirements {
+        if matches!(check.status, RequirementStatus::Satisfied(_)) {
+            continue;
+        }
+        let requirement = format!("{}@{}", check.name, check.range);
+        if let Some(ecosystem) = ecosystem {
+            needed.add_dependency(DependencyType::from_ecosystem(*ecosystem, requirement));
+            continue;
+        }
+        match kind {
+            DependencyKind::Peer => {
+                println!(
+                    "{} {} is a peer dependency, the project has to provide it",
+                    "⚠".yellow().bold(),
+                    requirement
+                );
+                peers.push(requirement);
+                continue;
+            }
+            DependencyKind::Optional => {
+                optional.push((requirement, reason.clone()));
+                continue;
+            }
+            DependencyKind::Dev => {
+                dev.insert(check.name.clone());
+            }
+            DependencyKind::Runtime => {
+                // Upgrades stay in the section the project declares them in
+                let declared_dev = packages.declared.get(&check.name).is_some_and(|d| d.section == "devDependencies");
+                if declared_dev || check.name.starts_with("@types/") {
+                    dev.insert(check.name.clone());
+                }
+            }
+        }
+        needed.add_dependency(DependencyType::npm(&requirement));
+    }
+
+    if !optional.is_empty() {
+        println!("{} Optional dependencies:", "ℹ".blue().bold());
+        for (requirement, reason) in &optional {
+            match reason {
+                Some(reason) => println!("  {} ({})", requirement, reason),
+                None => println!("  {}", requirement),
+            }
+        }
+        if ask_optional {
+            let options: Vec<String> = optional.into_iter().map(|(requirement, _)| requirement).collect();
+            let chosen = answers::multi_select(
+                "install.optional",
+                MultiSelect::new("Which optional dependencies should be installed?", options),
+            )
+            .context("Failed to choose optional dependencies")?;
+            for requirement in chosen {
+                needed.add_dependency(DependencyType::npm(&requirement));
+            }
+        }
+    }
+
+    let manager = PackageManager::detect(project_dir, project_config.package_manager.as_deref());
+    let workspace = Workspace::find(project_dir);
+    let mut commands = needed.install_commands(manager, workspace.as_ref(), &dev);
+    commands.extend(needed.ecosystem_install_commands());
+
+    let npm_requirements = needed.dependencies.iter().filter_map(DependencyType::npm_requirement).map(|(r, _)| r);
+    let manifest = npm_requirements
+        .map(|requirement| {
+            let section = if dev.contains(split_npm(&requirement).0) { "devDependencies" } else { "dependencies" };
+            (requirement, section)
+        })
+        .chain(peers.into_iter().map(|requirement| (requirement, "peerDependencies")))
+        .map(|(requirement, section)| {
+            let (name, range) = split_npm(&requirement);
+            let declared = packages.declared.get(name);
+            // Keep what the project declares when it also satisfies the components
+            let range = declared
+                .and_then(|d| intersect_requirements(&format!("{}@{}", name, d.range), &requirement))
+                .map_or(range.to_string(), |narrowest| split_npm(&narrowest).1.to_string());
+            ManifestEntry {
+                section: declared.map_or(section.to_string(), |d| d.section.clone()),
+                name: name.to_string(),
+                range,
+            }
+        })
+        .collect();
+    Ok(InstallPlan { commands, manifest })
+}
+
+fn print_requirement_table(requirements: &[Requirement]) {
+    let found = |check: &RequirementCheck| match &check.status {
+        RequirementStatus::Satisfied(found) | RequirementStatus::Mismatch(found) => found.clone(),
+        RequirementStatus::Missing => "-".to_string(),
+    };
+    let checks = || requirements.iter().map(|r| &r.check);
+    let name_width = checks().map(|c| c.name.len()).max().unwrap_or(0).max("Package".len());
+    let range_width = checks().map(|c| c.range.len()).max().unwrap_or(0).max("Required".len());
+    let found_width = checks().map(|c| found(c).len()).max().unwrap_or(0).max("Project".len());
+
+    println!(
+        "{:<name_width$}  {:<8}  {:<8}  {:<range_width$}  {:<found_width$}  Status",
+        "Package", "Source", "Kind", "Required", "Project"
+    );
+    for Requirement { ecosystem, kind, check, .. } in requirements {
+        let status = match check.status {
+            RequirementStatus::Satisfied(_) => "satisfied".green(),
+            RequirementStatus::Mismatch(_) => "version mismatch".yellow(),
+            RequirementStatus::Missing => "missing".red(),
+        };
+        println!(
+            "{:<name_width$}  {:<8}  {:<8}  {:<range_width$}  {:<found_width$}  {}",
+            check.name, ecosystem.map_or("npm", |e| e.name()), kind.to_string(), check.range, found(check), status
+        );
+    }
+
+    let conflicts = checks().filter(|c| matches!(c.status, RequirementStatus::Mismatch(_))).count();
+    if conflicts > 0 {
+        println!(
+            "\n{} {} of the project's existing versions conflict with the components' requirements",
+            "⚠".yellow().bold(),
+            conflicts
+        );
+    }
+    println!();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
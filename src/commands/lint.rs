@@ -0,0 +1,521 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use regex::Regex;
+
+use crate::config::{get_config, LintConfig, Severity};
+use crate::library::{self, LibraryComponent};
+use crate::source::import_specifiers;
+use crate::taxonomy::Taxonomy;
+use crate::utils::wildcard_match;
+
+/// Built-in rules and their default severity.
+pub const RULES: &[(&str, Severity)] = &[
+    ("required-fields", Severity::Error),
+    ("empty-description", Severity::Warning),
+    ("file-name", Severity::Error),
+    ("no-absolute-imports", Severity::Error),
+    ("no-console", Severity::Warning),
+    ("max-lines", Severity::Warning),
+];
+
+/// Fields `component.toml` must have, with the value `--fix` fills in when
+/// one can be derived.
+const REQUIRED_FIELDS: &[&str] = &[
+    "name", "version", "framework", "style", "language", "description", "author", "created_at", "updated_at", "tags",
+];
+
+const STYLE_EXTENSIONS: &[&str] = &["css", "scss"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub path: PathBuf,
+    /// 1-based line the problem is on
+    pub line: usize,
+    pub severity: Severity,
+    pub rule: String,
+    pub message: String,
+    /// Whether `lint --fix` resolves it
+    pub fixable: bool,
+}
+
+/// The built-in rules plus the project's custom ones, with their severities
+/// resolved from the configuration.
+pub struct Linter {
+    max_lines: usize,
+    severities: Vec<(&'static str, Severity)>,
+    custom: Vec<CompiledRule>,
+    /// Decides which files are source files worth linting
+    taxonomy: Taxonomy,
+}
+
+struct CompiledRule {
+    name: String,
+    pattern: Regex,
+    message: String,
+    severity: Severity,
+    files: Vec<String>,
+    replace: Option<String>,
+}
+
+impl Linter {
+    pub fn new(config: &LintConfig, taxonomy: Taxonomy) -> Result<Self> {
+        for name in config.rules.keys() {
+            if !RULES.iter().any(|(rule, _)| rule == name) {
+                bail!(
+                    "Unknown lint rule '{}', expected one of: {}",
+                    name,
+                    RULES.iter().map(|(rule, _)| *rule).collect::<Vec<_>>().join(", ")
+                );
+            }
+        }
+        let severities = RULES.iter()
+            .map(|(rule, default)| (*rule, config.rules.get(*rule).copied().unwrap_or(*default)))
+            .collect();
+        let custom = config.custom.iter()
+            .map(|rule| {
+                let pattern = Regex::new(&rule.pattern)
+                    .with_context(|| format!("Invalid pattern for lint rule '{}'", rule.name))?;
+                Ok(CompiledRule {
+                    name: rule.name.clone(),
+                    message: rule.message.clone().unwrap_or_else(|| format!("matches `{}`", rule.pattern)),
+                    pattern,
+                    severity: rule.severity,
+                    files: rule.files.clone(),
+                    replace: rule.replace.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { max_lines: config.max_lines, severities, custom, taxonomy })
+    }
+
+    fn severity(&self, rule: &str) -> Severity {
+        self.severities.iter().find(|(name, _)| *name == rule).map_or(Severity::Off, |(_, severity)| *severity)
+    }
+
+    /// Every problem found in `component`, in file and line order.
+    pub fn lint(&self, component: &LibraryComponent) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let mut report = |path: &Path, line: usize, rule: &str, severity: Severity, message: String, fixable: bool| {
+            if severity != Severity::Off {
+                findings.push(Finding { path: path.to_path_buf(), line, severity, rule: rule.to_string(), message, fixable });
+            }
+        };
+
+        if component.path.is_dir() {
+            let metadata_path = component.metadata_path();
+            match fs::read_to_string(&metadata_path) {
+                Err(_) => report(&metadata_path, 1, "required-fields", self.severity("required-fields"), "missing component.toml".to_string(), false),
+                Ok(content) => match content.parse::<toml::Table>() {
+                    Err(e) => {
+                        let message = format!("invalid TOML: {}", e.message());
+                        let line = e.span().map_or(1, |span| line_of(&content, span.start));
+                        report(&metadata_path, line, "required-fields", self.severity("required-fields"), message, false);
+                    }
+                    Ok(table) => {
+                        for field in REQUIRED_FIELDS.iter().filter(|f| !table.contains_key(**f)) {
+                            let fixable = default_field(component, field).is_some();
+                            let message = format!("missing required field `{}`", field);
+                            report(&metadata_path, 1, "required-fields", self.severity("required-fields"), message, fixable);
+                        }
+                        if let Some(description) = table.get("description").and_then(|d| d.as_str())
+                            && description.trim().is_empty()
+                        {
+                            let line = field_line(&content, "description");
+                            let message = "description is empty".to_string();
+                            report(&metadata_path, line, "empty-description", self.severity("empty-description"), message, false);
+                        }
+                    }
+                },
+            }
+
+            let (main_files, fixable) = misnamed_main_files(component, &self.taxonomy);
+            for file in main_files.iter().take(1) {
+                let message = format!("file name does not match the component name `{}`", component.name);
+                report(file, 1, "file-name", self.severity("file-name"), message, fixable);
+            }
+        }
+
+        for file in &component.files {
+            let ext = extension(file);
+            if !self.taxonomy.supports_extension(&ext) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(file) else {
+                continue;
+            };
+            let lines: Vec<&str> = content.lines().collect();
+
+            for specifier in import_specifiers(&ext, &content).iter().filter(|s| is_absolute(s)) {
+                let line = lines.iter()
+                    .position(|l| l.contains(&format!("'{}'", specifier)) || l.contains(&format!("\"{}\"", specifier)))
+                    .map_or(1, |i| i + 1);
+                let message = format!("absolute import `{}`, use a relative path", specifier);
+                report(file, line, "no-absolute-imports", self.severity("no-absolute-imports"), message, false);
+            }
+
+            for (i, line) in lines.iter().enumerate() {
+                if has_console_log(line) {
+                    let message = "unexpected console.log".to_string();
+                    report(file, i + 1, "no-console", self.severity("no-console"), message, is_console_statement(line));
+                }
+            }
+
+            if lines.len() > self.max_lines {
+                let message = format!("file has {} lines, more than the maximum of {}", lines.len(), self.max_lines);
+                report(file, self.max_lines + 1, "max-lines", self.severity("max-lines"), message, false);
+            }
+
+            for rule in self.custom.iter().filter(|rule| rule.applies_to(file)) {
+                for (i, line) in lines.iter().enumerate() {
+                    if rule.pattern.is_match(line) {
+                        report(file, i + 1, &rule.name, rule.severity, rule.message.clone(), rule.replace.is_some());
+                    }
+                }
+            }
+        }
+
+        findings.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        findings
+    }
+
+    /// Applies the automatic fixes to `component`, returning how many
+    /// problems were fixed.
+    pub fn fix(&self, component: &LibraryComponent) -> Result<usize> {
+        let mut fixed = 0;
+
+        for file in &component.files {
+            if !self.taxonomy.supports_extension(&extension(file)) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(file) else {
+                continue;
+            };
+            let mut out = String::with_capacity(content.len());
+            for line in content.split_inclusive('\n') {
+                let text = line.trim_end_matches(['\n', '\r']);
+                if self.severity("no-console") != Severity::Off && is_console_statement(text) {
+                    fixed += 1;
+                    continue;
+                }
+                let mut text = text.to_string();
+                for rule in self.custom.iter().filter(|rule| rule.severity != Severity::Off && rule.applies_to(file)) {
+                    if let Some(replacement) = &rule.replace
+                        && rule.pattern.is_match(&text)
+                    {
+                        text = rule.pattern.replace_all(&text, replacement.as_str()).into_owned();
+                        fixed += 1;
+                    }
+                }
+                out.push_str(&text);
+                out.push_str(&line[line.trim_end_matches(['\n', '\r']).len()..]);
+            }
+            if out != content {
+                fs::write(file, out).with_context(|| format!("Failed to write {}", file.display()))?;
+            }
+        }
+
+        if component.path.is_dir() && self.severity("required-fields") != Severity::Off {
+            let metadata_path = component.metadata_path();
+            if let Ok(content) = fs::read_to_string(&metadata_path)
+                && let Ok(table) = content.parse::<toml::Table>()
+            {
+                let mut added = String::new();
+                for field in REQUIRED_FIELDS.iter().filter(|f| !table.contains_key(**f)) {
+                    if let Some(value) = default_field(component, field) {
+                        added.push_str(&format!("{} = {}\n", field, value));
+                        fixed += 1;
+                    }
+                }
+                if !added.is_empty() {
+                    // Top-level keys have to come before the first table
+                    fs::write(&metadata_path, format!("{}{}", added, content))
+                        .with_context(|| format!("Failed to write {}", metadata_path.display()))?;
+                }
+            }
+        }
+
+        if component.path.is_dir() && self.severity("file-name") != Severity::Off {
+            let (main_files, fixable) = misnamed_main_files(component, &self.taxonomy);
+            if let [file] = main_files.as_slice()
+                && fixable
+            {
+                let renamed = file.with_file_name(format!("{}.{}", component.name, extension(file)));
+                fs::rename(file, &renamed)
+                    .with_context(|| format!("Failed to rename {} to {}", file.display(), renamed.display()))?;
+                fixed += 1;
+            }
+        }
+
+        Ok(fixed)
+    }
+}
+
+impl CompiledRule {
+    fn applies_to(&self, file: &Path) -> bool {
+        let file_name = file.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        self.files.is_empty() || self.files.iter().any(|pattern| wildcard_match(pattern, file_name))
+    }
+}
+
+/// The TOML value `--fix` uses for a missing field, for fields that can be
+/// derived from the component's place in the library.
+fn default_field(component: &LibraryComponent, field: &str) -> Option<String> {
+    let quoted = |value: &str| toml::Value::String(value.to_string()).to_string();
+    match field {
+        "name" => Some(quoted(&component.name)),
+        "framework" => Some(quoted(&component.framework)),
+        "style" => Some(quoted(&component.style)),
+        "version" => Some(quoted("0.1.0")),
+        "author" => Some(quoted("")),
+        "created_at" | "updated_at" => Some(quoted(&Utc::now().to_rfc3339())),
+        "tags" => Some("[]".to_string()),
+        _ => None,
+    }
+}
+
+/// The top-level source files of a component when none of them is named
+/// after it, and whether renaming can fix that (there is only one).
+fn misnamed_main_files(component: &LibraryComponent, taxonomy: &Taxonomy) -> (Vec<PathBuf>, bool) {
+    let main_files: Vec<PathBuf> = component.files.iter()
+        .filter(|f| f.parent() == Some(component.path.as_path()))
+        .filter(|f| {
+            let ext = extension(f);
+            taxonomy.supports_extension(&ext) && !STYLE_EXTENSIONS.contains(&ext.as_str())
+        })
+        .cloned()
+        .collect();
+    let named = main_files.iter().any(|f| f.file_stem().and_then(|s| s.to_str()) == Some(component.name.as_str()));
+    if named || main_files.is_empty() {
+        return (Vec::new(), false);
+    }
+    let fixable = main_files.len() == 1 && !main_files[0].with_file_name(format!("{}.{}", component.name, extension(&main_files[0]))).exists();
+    (main_files, fixable)
+}
+
+fn extension(path: &Path) -> String {
+    path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase()
+}
+
+fn is_absolute(specifier: &str) -> bool {
+    let bytes = specifier.as_bytes();
+    specifier.starts_with('/')
+        || specifier.starts_with('\\')
+        || (bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'/' | b'\\'))
+}
+
+fn has_console_log(line: &str) -> bool {
+    line.find("console.log(").is_some_and(|i| !line[..i].contains("//"))
+}
+
+/// Whether the line is nothing but a `console.log(...)` call, so removing it
+/// leaves the code intact: the call's parentheses close at the end of the
+/// line, followed by at most a `;`.
+fn is_console_statement(line: &str) -> bool {
+    let Some(arguments) = line.trim().strip_prefix("console.log(") else {
+        return false;
+    };
+    let mut depth = 1;
+    let mut quote: Option<char> = None;
+    let mut chars = arguments.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return matches!(&arguments[i + 1..], "" | ";");
+                }
+            }
+            (None, _) => {}
+        }
+    }
+    false
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+fn field_line(content: &str, field: &str) -> usize {
+    content.lines()
+        .position(|line| line.trim_start().strip_prefix(field).is_some_and(|rest| rest.trim_start().starts_with('=')))
+        .map_or(1, |i| i + 1)
+}
+
+/// Lints one component, or the whole library, printing each problem as
+/// `file:line`. With `fix`, the fixable problems are fixed first. Fails when
+/// any error remains.
+pub fn lint_components(component: Option<&str>, fix: bool) -> Result<()> {
+    let config = get_config().map_err(anyhow::Error::msg)?;
+    let linter = Linter::new(&config.lint, Taxonomy::load(&config.taxonomy)?)?;
+    let components_dir = Path::new(&config.components_dir);
+
+    let select = |components: &[LibraryComponent]| -> Result<Vec<LibraryComponent>> {
+        Ok(match component {
+            Some(name) => vec![library::find(components, name)?.clone()],
+            None => components.to_vec(),
+        })
+    };
+    let mut targets = select(&library::scan(components_dir))?;
+    if targets.is_empty() {
+        println!("{} No components found in {}", "ℹ".blue().bold(), components_dir.display());
+        return Ok(());
+    }
+
+    if fix {
+        let mut fixed = 0;
+        for target in &targets {
+            fixed += linter.fix(target)?;
+        }
+        if fixed > 0 {
+            println!("{} Fixed {} problem(s)", "✓".green().bold(), fixed);
+            // Renamed files change the component listings
+            let ids: Vec<String> = targets.iter().map(|t| t.id()).collect();
+            targets = library::scan(components_dir).into_iter().filter(|c| ids.contains(&c.id())).collect();
+        }
+    }
+
+    let findings: Vec<Finding> = targets.iter().flat_map(|t| linter.lint(t)).collect();
+    for finding in &findings {
+        let severity = match finding.severity {
+            Severity::Error => "error".red().bold(),
+            _ => "warning".yellow().bold(),
+        };
+        println!("{}:{}: {} {} [{}]", finding.path.display(), finding.line, severity, finding.message, finding.rule);
+    }
+
+    let errors = findings.iter().filter(|f| f.severity == Severity::Error).count();
+    let warnings = findings.len() - errors;
+    let fixable = findings.iter().filter(|f| f.fixable).count();
+    if findings.is_empty() {
+        println!("{} No problems found in {} component(s)", "✓".green().bold(), targets.len());
+        return Ok(());
+    }
+    println!();
+    println!("{} error(s), {} warning(s)", errors, warnings);
+    if fixable > 0 && !fix {
+        println!("{} {} problem(s) can be fixed with --fix", "ℹ".blue().bold(), fixable);
+    }
+    if errors > 0 {
+        bail!("Lint found {} error(s)", errors);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CustomRule;
+
+    fn component(root: &Path, name: &str, file: &str, source: &str, metadata: &str) -> LibraryComponent {
+        let dir = root.join("vue").join("tailwind").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(file), source).unwrap();
+        fs::write(dir.join("component.toml"), metadata).unwrap();
+        library::find(&library::scan(root), name).unwrap().clone()
+    }
+
+    fn rules(findings: &[Finding]) -> Vec<(String, usize, bool)> {
+        findings.iter().map(|f| (f.rule.clone(), f.line, f.fixable)).collect()
+    }
+
+    #[test]
+    fn test_lint_and_fix() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let source = "<script setup>\nimport Icon from '/home/me/Icon.vue'\nconsole.log('mounted')\nconst a = 1 // console.log(a)\n</script>\n<style>\np { color: red !important; }\n</style>\n";
+        let metadata = "version = \"1.0.0\"\nframework = \"vue\"\nstyle = \"tailwind\"\nlanguage = \"javascript\"\ndescription = \" \"\nauthor = \"\"\ncreated_at = \"\"\nupdated_at = \"\"\ntags = []\n\n[dependencies]\ndependencies = []\n";
+        let button = component(root, "Button", "MyButton.vue", source, metadata);
+        let config = LintConfig {
+            max_lines: 5,
+            custom: vec![CustomRule {
+                name: "no-important".to_string(),
+                pattern: "!important".to_string(),
+                message: None,
+                severity: Severity::Error,
+                files: vec!["*.vue".to_string()],
+                replace: Some(String::new()),
+            }],
+            ..LintConfig::default()
+        };
+        let linter = Linter::new(&config, Taxonomy::builtin()).unwrap();
+
+        assert_eq!(
+            rules(&linter.lint(&button)),
+            [
+                ("file-name".to_string(), 1, true),
+                ("no-absolute-imports".to_string(), 2, false),
+                ("no-console".to_string(), 3, true),
+                ("max-lines".to_string(), 6, false),
+                ("no-important".to_string(), 7, true),
+                ("required-fields".to_string(), 1, true),
+                ("empty-description".to_string(), 5, false),
+            ]
+        );
+
+        assert_eq!(linter.fix(&button).unwrap(), 4);
+        let button = library::find(&library::scan(root), "Button").unwrap().clone();
+        assert_eq!(
+            rules(&linter.lint(&button)),
+            [
+                ("no-absolute-imports".to_string(), 2, false),
+                ("max-lines".to_string(), 6, false),
+                ("empty-description".to_string(), 6, false),
+            ]
+        );
+        let fixed = fs::read_to_string(root.join("vue/tailwind/Button/Button.vue")).unwrap();
+        assert!(!fixed.contains("console.log('mounted')"));
+        assert!(fixed.contains("p { color: red ; }"));
+        assert!(fs::read_to_string(button.metadata_path()).unwrap().starts_with("name = \"Button\"\n"));
+
+        let mut config = LintConfig::default();
+        config.rules.insert("no-console".to_string(), Severity::Off);
+        let quiet = component(root, "Quiet", "Quiet.vue", "console.log(1)\n", metadata);
+        assert_eq!(rules(&Linter::new(&config, Taxonomy::builtin()).unwrap().lint(&quiet)), [("required-fields".to_string(), 1, true), ("empty-description".to_string(), 5, false)]);
+
+        // Only calls that make up the whole statement are removed
+        let chained = component(root, "Chained", "Chained.vue", "console.log(a); save()\nconsole.log(x) || reset()\nconsole.log('(', f(b));\n", metadata);
+        let linter = Linter::new(&LintConfig::default(), Taxonomy::builtin()).unwrap();
+        let console: Vec<(String, usize, bool)> = rules(&linter.lint(&chained)).into_iter().filter(|(rule, _, _)| rule == "no-console").collect();
+        assert_eq!(
+            console,
+            [
+                ("no-console".to_string(), 1, false),
+                ("no-console".to_string(), 2, false),
+                ("no-console".to_string(), 3, true),
+            ]
+        );
+        linter.fix(&chained).unwrap();
+        let fixed = fs::read_to_string(root.join("vue/tailwind/Chained/Chained.vue")).unwrap();
+        assert_eq!(fixed, "console.log(a); save()\nconsole.log(x) || reset()\n");
+
+        config.rules.insert("no-such-rule".to_string(), Severity::Off);
+        assert!(Linter::new(&config, Taxonomy::builtin()).is_err());
+    }
+
+    #[test]
+    fn test_lint_taxonomy_extensions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let metadata = "name = \"Card\"\nversion = \"1.0.0\"\nframework = \"vue\"\nstyle = \"tailwind\"\nlanguage = \"astro\"\ndescription = \"A card\"\nauthor = \"\"\ncreated_at = \"\"\nupdated_at = \"\"\ntags = []\n\n[dependencies]\ndependencies = []\n";
+        let card = component(temp_dir.path(), "Card", "Card.astro", "---\nconsole.log(Astro.props)\n---\n", metadata);
+
+        // Files the taxonomy does not know are not source files
+        let linter = Linter::new(&LintConfig::default(), Taxonomy::builtin()).unwrap();
+        assert_eq!(rules(&linter.lint(&card)), []);
+
+        let mut taxonomy = Taxonomy::builtin();
+        taxonomy.merge(&toml::from_str("[languages.astro]\nextensions = [\"astro\"]").unwrap());
+        let linter = Linter::new(&LintConfig::default(), taxonomy).unwrap();
+        assert_eq!(rules(&linter.lint(&card)), [("no-console".to_string(), 2, true)]);
+    }
+}
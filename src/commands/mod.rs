@@ -4,3 +4,9 @@ pub mod init;
 pub mod show;
 pub mod install;
 pub mod dependencies;
+pub mod graph;
+pub mod dependents;
+pub mod deps;
+pub mod lint;
+pub mod fsck;
+pub mod audit;
@@ -6,9 +6,10 @@ use serde::Deserialize;
 use toml;
 
 use crate::config::get_config;
-use crate::utils::{SUPPORTED_FRAMEWORKS, SUPPORTED_STYLES};
+use crate::taxonomy::{self, Taxonomy};
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)] // mirrors the full component.toml schema
 struct ComponentMetadata {
     name: String,
     version: String,
@@ -83,7 +84,7 @@ pub fn show_components(show_all: bool) {
         println!("Framework: {:?}", project_config.framework);
         println!("Style: {:?}", project_config.style);
         println!("Language: {:?}", project_config.language);
-        println!("{:-<40} {}", "-", "-");
+        println!("{:-<40} -", "-");
         
         let mut has_components = false;
         
@@ -102,30 +103,29 @@ pub fn show_components(show_all: bool) {
                 
                 if let Ok(component_dirs) = fs::read_dir(&style_dir) {
                     for component_dir in component_dirs.flatten() {
-                        if let Ok(dir_type) = component_dir.file_type() {
-                            if dir_type.is_dir() {
-                                if let Some(component_name) = component_dir.file_name().to_str() {
-                                    let component_path = component_dir.path();
-                                    let config_path = component_path.join("component.toml");
-                                    
-                                    if config_path.exists() {
-                                        match get_component_config(&component_path) {
-                                            Some(component_config) => {
-                                                if is_compatible(&component_config, &project_config) {
-                                                    println!("- {}/{}/{}", framework, style, component_name);
-                                                    has_components = true;
-                                                }
-                                            }
-                                            None => {
-                                                println!("⚠️  Invalid component config in {}/{}/{}", framework, style, component_name);
-                                            }
+                        if let Ok(dir_type) = component_dir.file_type()
+                            && dir_type.is_dir()
+                            && let Some(component_name) = component_dir.file_name().to_str()
+                        {
+                            let component_path = component_dir.path();
+                            let config_path = component_path.join("component.toml");
+                            
+                            if config_path.exists() {
+                                match get_component_config(&component_path) {
+                                    Some(component_config) => {
+                                        if is_compatible(&component_config, &project_config) {
+                                            println!("- {}/{}/{}", framework, style, component_name);
+                                            has_components = true;
                                         }
-                                    } else {
-                                        // If no config found, just show the component
-                                        println!("- {}/{}/{}", framework, style, component_name);
-                                        has_components = true;
+                                    }
+                                    None => {
+                                        println!("⚠️  Invalid component config in {}/{}/{} (run `fsck` for details)", framework, style, component_name);
                                     }
                                 }
+                            } else {
+                                // If no config found, just show the component
+                                println!("- {}/{}/{}", framework, style, component_name);
+                                has_components = true;
                             }
                         }
                     }
@@ -147,26 +147,29 @@ pub fn show_components(show_all: bool) {
             println!("Error processing components directory: {}", e);
         }
         
-        // Sort frameworks and styles for consistent output
+        // Sort frameworks and styles in taxonomy order for consistent output
+        let known = match Taxonomy::load(&project_config.taxonomy) {
+            Ok(known) => known,
+            Err(e) => {
+                println!("Error loading global config: {:#}", e);
+                Taxonomy::builtin()
+            }
+        };
         let mut sorted_frameworks: Vec<_> = components_by_framework.keys().collect();
-        sorted_frameworks.sort_by_key(|f| {
-            SUPPORTED_FRAMEWORKS.iter().position(|&x| x == **f).unwrap_or(usize::MAX)
-        });
+        sorted_frameworks.sort_by_key(|f| taxonomy::position(&known.frameworks, f));
         
         for framework in sorted_frameworks {
             let styles = components_by_framework.get(framework).unwrap();
-            println!("{}:", framework);
+            println!("{}:", display_name(&known.frameworks, framework));
             
             // Sort styles
             let mut sorted_styles: Vec<_> = styles.keys().collect();
-            sorted_styles.sort_by_key(|s| {
-                SUPPORTED_STYLES.iter().position(|&x| x == **s).unwrap_or(usize::MAX)
-            });
+            sorted_styles.sort_by_key(|s| taxonomy::position(&known.styles, s));
             
             for style in sorted_styles {
                 let components = styles.get(style).unwrap();
                 if !components.is_empty() {
-                    println!("  - {} ({}):", style, components.len());
+                    println!("  - {} ({}):", display_name(&known.styles, style), components.len());
                     for component in components {
                         println!("    • {}", component);
                     }
@@ -177,6 +180,15 @@ pub fn show_components(show_all: bool) {
     }
 }
 
+/// The entry's display name followed by its id, or just the id for unknown
+/// entries and names that only differ in case.
+fn display_name(entries: &[taxonomy::Entry], id: &str) -> String {
+    match taxonomy::find(entries, id) {
+        Some(entry) if !entry.name.eq_ignore_ascii_case(id) => format!("{} [{}]", entry.name, id),
+        _ => id.to_string(),
+    }
+}
+
 // TODO combine the following functions into one
 /// Process all components in the components directory and populate the components map
 fn process_components_directory(
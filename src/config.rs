@@ -1,5 +1,9 @@
 use serde::{Serialize, Deserialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The project configuration, in the directory commands run from.
+pub const CONFIG_FILE: &str = ".component-manager.toml";
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ProjectConfig {
@@ -8,17 +12,325 @@ pub struct ProjectConfig {
     pub language: Vec<String>,
     #[serde(default = "default_components_dir")]
     pub components_dir: PathBuf,
+    /// Default destination for imported components, same as `[import] dir`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub import: ImportConfig,
+    /// Package manager used to install dependencies (`npm`, `yarn`, `pnpm` or
+    /// `bun`), detected from the project when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<String>,
+    /// Projects that import from this library, scanned by `dependents --projects`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<PathBuf>,
+    /// npm registry checked by `deps outdated`, the public one when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    #[serde(default, skip_serializing_if = "LintConfig::is_default")]
+    pub lint: LintConfig,
+    #[serde(default, skip_serializing_if = "AuditConfig::is_default")]
+    pub audit: AuditConfig,
+    #[serde(default, skip_serializing_if = "SecretsConfig::is_default")]
+    pub secrets: SecretsConfig,
+    #[serde(default, skip_serializing_if = "TaxonomyConfig::is_default")]
+    pub taxonomy: TaxonomyConfig,
+}
+
+/// Where imported components are placed in the project.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ImportConfig {
+    /// Default destination for imported components
+    #[serde(default, alias = "import_dir", skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+    /// Place each component in its own subfolder of the destination
+    #[serde(default)]
+    pub subfolders: bool,
+    /// Destinations by component kind or tag, e.g. `composables = "src/composables"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub kinds: BTreeMap<String, PathBuf>,
+    /// Destinations by framework, for repositories with several stacks
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub frameworks: BTreeMap<String, PathBuf>,
+}
+
+impl ImportConfig {
+    /// The configured destination for a component of the given framework,
+    /// ignoring kind and tag overrides.
+    pub fn default_dir(&self, framework: &str) -> Option<&Path> {
+        self.frameworks.get(framework)
+            .or(self.dir.as_ref())
+            .map(PathBuf::as_path)
+    }
+
+    /// The destination configured for a component's kind or one of its tags.
+    pub fn kind_dir<'a>(&self, kind: Option<&str>, tags: impl IntoIterator<Item = &'a String>) -> Option<&Path> {
+        kind.and_then(|k| self.kinds.get(k))
+            .or_else(|| tags.into_iter().find_map(|t| self.kinds.get(t)))
+            .map(PathBuf::as_path)
+    }
+}
+
+/// Rules applied by `lint`, on top of the built-in ones.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LintConfig {
+    /// Longest a component file may be, in lines
+    #[serde(default = "default_max_lines")]
+    pub max_lines: usize,
+    /// Severity overrides for built-in rules by name, e.g. `no-console = "off"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rules: BTreeMap<String, Severity>,
+    /// Rules matching a pattern against each line of the component files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<CustomRule>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self { max_lines: default_max_lines(), rules: BTreeMap::new(), custom: Vec::new() }
+    }
+}
+
+impl LintConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_max_lines() -> usize {
+    300
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Warning,
+    Error,
+}
+
+/// A project-specific lint rule, e.g.
+///
+/// ```toml
+/// [[lint.custom]]
+/// name = "no-important"
+/// pattern = "!important"
+/// message = "Avoid !important"
+/// replace = ""
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomRule {
+    pub name: String,
+    /// Regular expression matched against each line, in the `regex` crate's syntax
+    pub pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default = "default_custom_severity")]
+    pub severity: Severity,
+    /// File name patterns the rule applies to, e.g. `*.vue` (default: every file)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Replacement for the matched text, which makes the rule fixable. `$1`
+    /// or `${name}` insert a group of the match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace: Option<String>,
+}
+
+fn default_custom_severity() -> Severity {
+    Severity::Warning
+}
+
+/// How `audit` and `import` treat risky patterns in component source.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditConfig {
+    /// Findings at or above this risk fail `audit` and need confirmation on import
+    #[serde(default = "default_audit_threshold")]
+    pub threshold: Risk,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self { threshold: default_audit_threshold() }
+    }
+}
+
+impl AuditConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_audit_threshold() -> Risk {
+    Risk::Medium
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for Risk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Risk::Low => "low",
+            Risk::Medium => "medium",
+            Risk::High => "high",
+        })
+    }
+}
+
+/// What `export` treats as secrets or internal references, on top of the
+/// built-in token formats and high-entropy strings.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SecretsConfig {
+    /// Internal host names, with `*` wildcards (e.g. `*.corp.example.com`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<String>,
+    /// Internal IPv4 ranges in CIDR notation
+    #[serde(default = "default_secret_networks")]
+    pub networks: Vec<String>,
+    /// Values never reported, with `*` wildcards (e.g. `*@example.com`)
+    #[serde(default = "default_secret_allowlist")]
+    pub allow: Vec<String>,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self { hosts: Vec::new(), networks: default_secret_networks(), allow: default_secret_allowlist() }
+    }
+}
+
+impl SecretsConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_secret_networks() -> Vec<String> {
+    ["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"].map(String::from).to_vec()
+}
+
+fn default_secret_allowlist() -> Vec<String> {
+    ["*@example.com", "*@example.org", "*@example.net"].map(String::from).to_vec()
+}
+
+/// Frameworks, styles and languages added to or changed from the built-in
+/// ones, by id, e.g.
+///
+/// ```toml
+/// [taxonomy.styles.unocss]
+/// name = "UnoCSS"
+/// dependencies = { unocss = "^0.58.0" }
+/// markers = ["uno.css", "i-carbon-"]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaxonomyConfig {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub frameworks: BTreeMap<String, TaxonomyEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub styles: BTreeMap<String, TaxonomyEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub languages: BTreeMap<String, TaxonomyEntry>,
+}
+
+impl TaxonomyConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// One framework, style or language. Fields that are set replace the values
+/// of a built-in entry with the same id.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaxonomyEntry {
+    /// Name shown to users, e.g. `styled-components` or `Vuetify`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Extensions of the component files it applies to, used by `export` to
+    /// infer it from a file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+    /// npm packages every component using it requires, with their range
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+    /// Text in a component's source that marks it as using this entry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub markers: Vec<String>,
 }
 
 fn default_components_dir() -> PathBuf {
     PathBuf::from("./components")
 }
 
+/// A named starting point for `init --preset`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Preset {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub framework: Vec<String>,
+    pub style: Vec<String>,
+    pub language: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components_dir: Option<PathBuf>,
+}
+
+/// Settings shared by every project on the machine, read from
+/// `$COMPONENT_MANAGER_CONFIG` or `~/.config/component-manager/config.toml`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct GlobalConfig {
+    /// User-defined presets for `init --preset`, by name
+    #[serde(default)]
+    pub presets: BTreeMap<String, Preset>,
+    /// Frameworks, styles and languages for every project, see [`TaxonomyConfig`]
+    #[serde(default)]
+    pub taxonomy: TaxonomyConfig,
+}
+
+impl GlobalConfig {
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("COMPONENT_MANAGER_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_home.join("component-manager").join("config.toml"))
+    }
+
+    /// Loads the global config, which is empty when the file does not exist.
+    pub fn load() -> anyhow::Result<Self> {
+        use anyhow::Context;
+        let Some(path) = Self::path().filter(|p| p.exists()) else {
+            return Ok(Self::default());
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+}
+
 impl ProjectConfig {
+    /// The configured destination for a component of the given framework:
+    /// its `[import.frameworks]` entry, `[import] dir`, or `import_dir`.
+    pub fn default_import_dir(&self, framework: &str) -> Option<&Path> {
+        self.import.default_dir(framework).or(self.import_dir.as_deref())
+    }
+
     pub fn load_from_file() -> Option<Self> {
-        let content = std::fs::read_to_string(".component-manager.toml").ok()?;
+        let content = std::fs::read_to_string(CONFIG_FILE).ok()?;
         toml::from_str(&content).ok()
     }
+
+    /// The directory holding the config file, which relative paths in it
+    /// are resolved against.
+    pub fn dir() -> PathBuf {
+        std::path::absolute(CONFIG_FILE).ok()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .unwrap_or_default()
+    }
 }
 
 pub fn get_config() -> Result<ProjectConfig, String> {
@@ -30,6 +342,15 @@ pub fn get_config() -> Result<ProjectConfig, String> {
                 style: vec!["css".to_string()],
                 language: vec!["javascript".to_string()],
                 components_dir: default_components_dir(),
+                import_dir: None,
+                import: ImportConfig::default(),
+                package_manager: None,
+                projects: Vec::new(),
+                registry: None,
+                lint: LintConfig::default(),
+                audit: AuditConfig::default(),
+                secrets: SecretsConfig::default(),
+                taxonomy: TaxonomyConfig::default(),
             })
         })
         .ok_or_else(|| "Failed to load or create config".to_string())
@@ -0,0 +1,636 @@
+//! Package ecosystems other than npm: pip, composer, gem, cargo, go and pub.
+//!
+//! Requirements are stored as `name@spec`, where the spec keeps the native
+//! syntax of the ecosystem (`requests@>=2.31,<3`, `rails@~> 7.1`). For each
+//! ecosystem this module finds packages in source imports, reads what a
+//! project declares in its manifest and builds the install command.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::commands::dependencies::split_npm;
+use crate::npm::{RequirementCheck, RequirementStatus};
+use crate::package_manager::InstallCommand;
+use crate::semver::Range;
+use crate::utils::find_upwards;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Ecosystem {
+    Pip,
+    Composer,
+    Gem,
+    Cargo,
+    Go,
+    Pub,
+}
+
+const PYTHON_STDLIB: &[&str] = &[
+    "__future__", "abc", "argparse", "asyncio", "base64", "collections", "contextlib", "copy", "csv",
+    "dataclasses", "datetime", "decimal", "email", "enum", "functools", "glob", "hashlib", "html",
+    "http", "inspect", "io", "itertools", "json", "logging", "math", "operator", "os", "pathlib",
+    "pickle", "pprint", "queue", "random", "re", "shutil", "socket", "sqlite3", "statistics",
+    "string", "struct", "subprocess", "sys", "tempfile", "textwrap", "threading", "time",
+    "traceback", "types", "typing", "unittest", "urllib", "uuid", "warnings", "weakref", "xml", "zipfile",
+];
+
+/// Python modules whose distribution is named differently.
+const PYTHON_DISTRIBUTIONS: &[(&str, &str)] = &[
+    ("PIL", "pillow"),
+    ("bs4", "beautifulsoup4"),
+    ("cv2", "opencv-python"),
+    ("dateutil", "python-dateutil"),
+    ("dotenv", "python-dotenv"),
+    ("jwt", "pyjwt"),
+    ("rest_framework", "djangorestframework"),
+    ("sklearn", "scikit-learn"),
+    ("yaml", "pyyaml"),
+];
+
+const RUBY_STDLIB: &[&str] = &[
+    "base64", "benchmark", "bigdecimal", "csv", "date", "digest", "English", "erb", "fileutils",
+    "forwardable", "json", "logger", "net", "open3", "openssl", "optparse", "ostruct", "pathname",
+    "pp", "securerandom", "set", "singleton", "stringio", "tempfile", "time", "uri", "yaml",
+];
+
+impl Ecosystem {
+    /// The ecosystem of a source file, by extension.
+    pub fn for_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "py" => Some(Self::Pip),
+            "php" => Some(Self::Composer),
+            "rb" | "erb" => Some(Self::Gem),
+            "rs" => Some(Self::Cargo),
+            "go" => Some(Self::Go),
+            "dart" => Some(Self::Pub),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pip => "pip",
+            Self::Composer => "composer",
+            Self::Gem => "gem",
+            Self::Cargo => "cargo",
+            Self::Go => "go",
+            Self::Pub => "pub",
+        }
+    }
+
+    /// Manifests declaring the dependencies of a project, in lookup order.
+    pub fn manifests(&self) -> &'static [&'static str] {
+        match self {
+            Self::Pip => &["requirements.txt", "pyproject.toml"],
+            Self::Composer => &["composer.json"],
+            Self::Gem => &["Gemfile"],
+            Self::Cargo => &["Cargo.toml"],
+            Self::Go => &["go.mod"],
+            Self::Pub => &["pubspec.yaml"],
+        }
+    }
+
+    /// The packages a source file imports, as `name@spec` requirements with
+    /// the spec its project's manifest declares (`*` when unknown).
+    pub fn detect(&self, file: &Path, content: &str) -> Vec<String> {
+        let manifest = self.manifests().iter().find_map(|name| find_upwards(file, name));
+        let project_dir = manifest.as_deref().and_then(Path::parent).map(Path::to_path_buf);
+        let declared = project_dir.as_deref().map(|dir| self.declared(dir)).unwrap_or_default();
+        let local_dir = file.parent().unwrap_or(Path::new(""));
+
+        let mut packages: Vec<String> = Vec::new();
+        for import in self.imports(content) {
+            let Some(name) = self.package_for_import(&import, &declared, project_dir.as_deref(), local_dir) else {
+                continue;
+            };
+            let spec = declared.get(&name).map(String::as_str).unwrap_or("*");
+            let requirement = format!("{}@{}", name, spec);
+            if !packages.contains(&requirement) {
+                packages.push(requirement);
+            }
+        }
+        packages
+    }
+
+    /// Raw import paths of a source file.
+    fn imports(&self, content: &str) -> Vec<String> {
+        let mut imports = Vec::new();
+        // Inside a Go `import ( ... )` block
+        let mut in_block = false;
+        for line in content.lines() {
+            let line = line.trim();
+            match self {
+                Self::Pip => {
+                    if let Some(rest) = line.strip_prefix("from ") {
+                        imports.extend(rest.split_whitespace().next().map(str::to_string));
+                    } else if let Some(rest) = line.strip_prefix("import ") {
+                        imports.extend(rest.split(',').filter_map(|m| m.split_whitespace().next()).map(str::to_string));
+                    }
+                }
+                Self::Composer => {
+                    if let Some(rest) = line.strip_prefix("use ") {
+                        let rest = rest.trim_start_matches("function ").trim_start_matches("const ");
+                        imports.extend(rest.split([';', ' ', '{']).next().map(|n| n.trim_start_matches('\\').to_string()));
+                    }
+                }
+                Self::Gem => {
+                    let line = line.trim_start_matches("<%").trim();
+                    if let Some(rest) = line.strip_prefix("require ").or_else(|| line.strip_prefix("require(")) {
+                        imports.extend(first_quoted(rest));
+                    }
+                }
+                Self::Cargo => {
+                    let rest = line.strip_prefix("pub ").unwrap_or(line);
+                    if let Some(path) = rest.strip_prefix("use ").or_else(|| rest.strip_prefix("extern crate ")) {
+                        imports.push(path.trim_start_matches("::").to_string());
+                    }
+                }
+                Self::Go => {
+                    if line.starts_with("import (") {
+                        in_block = true;
+                    } else if in_block && line.starts_with(')') {
+                        in_block = false;
+                    } else if let Some(spec) = line.strip_prefix("import ").or(in_block.then_some(line)) {
+                        // An alias may come first: `import tw "github.com/..."`
+                        let spec = spec.trim_start_matches(|c: char| c != '"' && c != '`');
+                        imports.extend(first_quoted(&spec.replace('`', "\"")));
+                    }
+                }
+                Self::Pub => {
+                    if let Some(rest) = line.strip_prefix("import ").or_else(|| line.strip_prefix("export ")) {
+                        imports.extend(first_quoted(rest));
+                    }
+                }
+            }
+        }
+        imports
+    }
+
+    /// The package providing an import, or `None` for the standard library
+    /// and the project's own modules.
+    fn package_for_import(
+        &self,
+        import: &str,
+        declared: &BTreeMap<String, String>,
+        project_dir: Option<&Path>,
+        local_dir: &Path,
+    ) -> Option<String> {
+        let is_local = |module: &str| {
+            [Some(local_dir), project_dir].into_iter().flatten().any(|dir| {
+                dir.join(module).is_dir() || dir.join(format!("{}.py", module)).is_file() || dir.join(format!("{}.rb", module)).is_file()
+            })
+        };
+        match self {
+            Self::Pip => {
+                let module = import.split('.').next()?;
+                if module.is_empty() || PYTHON_STDLIB.contains(&module) || is_local(module) {
+                    return None;
+                }
+                let distribution = PYTHON_DISTRIBUTIONS.iter()
+                    .find(|(m, _)| *m == module)
+                    .map(|(_, d)| d.to_string())
+                    .unwrap_or_else(|| normalize_python_name(module));
+                Some(distribution)
+            }
+            Self::Composer => {
+                let namespace = import.split('\\').next()?.to_lowercase();
+                if namespace == "illuminate" {
+                    return Some("laravel/framework".to_string());
+                }
+                // Only packages the project requires can be told apart from its own namespaces
+                declared.keys()
+                    .find(|package| {
+                        let (vendor, name) = package.split_once('/').unwrap_or((package, ""));
+                        let squash = |s: &str| s.replace(['-', '_'], "");
+                        squash(vendor) == namespace || squash(name) == namespace
+                    })
+                    .cloned()
+            }
+            Self::Gem => {
+                let gem = import.split('/').next()?;
+                if gem.is_empty() || RUBY_STDLIB.contains(&gem) || is_local(gem) {
+                    return None;
+                }
+                Some(gem.to_string())
+            }
+            Self::Cargo => {
+                let krate = import.split("::").next()?.trim_end_matches(';').trim();
+                if matches!(krate, "std" | "core" | "alloc" | "crate" | "self" | "super") || krate.starts_with('{') {
+                    return None;
+                }
+                let dashed = krate.replace('_', "-");
+                if declared.contains_key(krate) {
+                    Some(krate.to_string())
+                } else if declared.contains_key(&dashed) {
+                    Some(dashed)
+                } else if is_local(krate) || local_dir.join(format!("{}.rs", krate)).is_file() {
+                    None
+                } else {
+                    Some(krate.to_string())
+                }
+            }
+            Self::Go => {
+                let first = import.split('/').next()?;
+                // Standard library packages have no dot in their first element
+                if !first.contains('.') {
+                    return None;
+                }
+                if let Some(own) = project_dir.and_then(go_module_path)
+                    && (import == own || import.starts_with(&format!("{}/", own)))
+                {
+                    return None;
+                }
+                let module = declared.keys()
+                    .filter(|module| import == module.as_str() || import.starts_with(&format!("{}/", module)))
+                    .max_by_key(|module| module.len())
+                    .cloned();
+                module.or_else(|| Some(import.split('/').take(3).collect::<Vec<_>>().join("/")))
+            }
+            Self::Pub => {
+                let package = import.strip_prefix("package:")?.split('/').next()?;
+                let own = project_dir.and_then(pubspec_name);
+                if matches!(package, "flutter" | "flutter_test") || own.as_deref() == Some(package) {
+                    return None;
+                }
+                Some(package.to_string())
+            }
+        }
+    }
+
+    /// What the project in `dir` declares, by package name. Specs are kept
+    /// in the ecosystem's syntax, with `*` for unconstrained packages.
+    pub fn declared(&self, dir: &Path) -> BTreeMap<String, String> {
+        let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+        let mut declared = BTreeMap::new();
+        match self {
+            Self::Pip => {
+                if let Some(content) = read("requirements.txt") {
+                    for line in content.lines() {
+                        let line = line.split('#').next().unwrap_or_default().split(';').next().unwrap_or_default().trim();
+                        if line.is_empty() || line.starts_with('-') {
+                            continue;
+                        }
+                        let (name, spec) = split_python_requirement(line);
+                        declared.insert(name, spec);
+                    }
+                }
+                if let Some(pyproject) = read("pyproject.toml").and_then(|c| c.parse::<toml::Table>().ok()) {
+                    let pep_508 = pyproject.get("project").and_then(|p| p.get("dependencies")).and_then(|d| d.as_array());
+                    for requirement in pep_508.into_iter().flatten().filter_map(|r| r.as_str()) {
+                        let (name, spec) = split_python_requirement(requirement.split(';').next().unwrap_or_default());
+                        declared.entry(name).or_insert(spec);
+                    }
+                    let poetry = pyproject.get("tool").and_then(|t| t.get("poetry")).and_then(|p| p.get("dependencies"));
+                    for (name, spec) in poetry.and_then(|d| d.as_table()).into_iter().flatten() {
+                        if name != "python" {
+                            declared.entry(normalize_python_name(name)).or_insert_with(|| toml_spec(spec));
+                        }
+                    }
+                }
+            }
+            Self::Composer => {
+                let manifest = read("composer.json").and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+                for section in ["require", "require-dev"] {
+                    let entries = manifest.as_ref().and_then(|m| m.get(section)).and_then(|s| s.as_object());
+                    for (name, spec) in entries.into_iter().flatten() {
+                        if name.contains('/') {
+                            declared.entry(name.clone()).or_insert_with(|| spec.as_str().unwrap_or("*").to_string());
+                        }
+                    }
+                }
+            }
+            Self::Gem => {
+                for line in read("Gemfile").unwrap_or_default().lines() {
+                    let Some(rest) = line.trim().strip_prefix("gem ") else {
+                        continue;
+                    };
+                    let mut arguments = rest.split(',').map(str::trim);
+                    let Some(name) = arguments.next().and_then(first_quoted) else {
+                        continue;
+                    };
+                    let constraints: Vec<String> = arguments
+                        .take_while(|a| a.starts_with(['"', '\'']))
+                        .filter_map(first_quoted)
+                        .collect();
+                    let spec = if constraints.is_empty() { "*".to_string() } else { constraints.join(", ") };
+                    declared.insert(name, spec);
+                }
+            }
+            Self::Cargo => {
+                let manifest = read("Cargo.toml").and_then(|c| c.parse::<toml::Table>().ok()).unwrap_or_default();
+                let workspace = manifest.get("workspace").and_then(|w| w.get("dependencies"));
+                let sections = ["dependencies", "dev-dependencies", "build-dependencies"].map(|s| manifest.get(s));
+                for table in sections.into_iter().chain([workspace]).flatten().filter_map(|t| t.as_table()) {
+                    for (name, spec) in table {
+                        declared.entry(name.clone()).or_insert_with(|| toml_spec(spec));
+                    }
+                }
+            }
+            Self::Go => {
+                let mut in_block = false;
+                for line in read("go.mod").unwrap_or_default().lines() {
+                    let line = line.split("//").next().unwrap_or_default().trim();
+                    let entry = if in_block {
+                        if line == ")" {
+                            in_block = false;
+                            continue;
+                        }
+                        line
+                    } else if line == "require (" {
+                        in_block = true;
+                        continue;
+                    } else if let Some(entry) = line.strip_prefix("require ") {
+                        entry
+                    } else {
+                        continue;
+                    };
+                    let mut parts = entry.split_whitespace();
+                    if let (Some(module), Some(version)) = (parts.next(), parts.next()) {
+                        declared.insert(module.to_string(), version.to_string());
+                    }
+                }
+            }
+            Self::Pub => {
+                let mut in_section = false;
+                for line in read("pubspec.yaml").unwrap_or_default().lines() {
+                    if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                        continue;
+                    }
+                    let indent = line.len() - line.trim_start().len();
+                    if indent == 0 {
+                        in_section = matches!(line.trim_end(), "dependencies:" | "dev_dependencies:");
+                        continue;
+                    }
+                    // Package names are indented by two; deeper lines describe sdk, path or git sources
+                    if in_section && indent == 2 && let Some((name, spec)) = line.trim().split_once(':') {
+                        let spec = spec.trim().trim_matches(['"', '\'']);
+                        let spec = if spec.is_empty() || spec == "any" { "*" } else { spec };
+                        declared.insert(name.trim().to_string(), spec.to_string());
+                    }
+                }
+            }
+        }
+        declared
+    }
+
+    /// Checks a `name@spec` requirement against what the project declares.
+    pub fn check(&self, declared: &BTreeMap<String, String>, requirement: &str) -> RequirementCheck {
+        let (name, spec) = split_npm(requirement);
+        let status = match declared.get(name) {
+            None => RequirementStatus::Missing,
+            Some(found) => {
+                let required = self.to_range(spec);
+                let lowest = self.to_range(found).and_then(|r| r.min_version());
+                match (required, lowest) {
+                    (Some(required), Some(lowest)) if !required.satisfies(&lowest) => {
+                        RequirementStatus::Mismatch(found.clone())
+                    }
+                    _ => RequirementStatus::Satisfied(found.clone()),
+                }
+            }
+        };
+        RequirementCheck { name: name.to_string(), range: spec.to_string(), status }
+    }
+
+    /// Converts a native version spec into an npm style range for comparison.
+    pub fn to_range(&self, spec: &str) -> Option<Range> {
+        let spec = spec.trim();
+        if spec.is_empty() || spec == "*" {
+            return Range::parse("*").ok();
+        }
+        let converted = match self {
+            Self::Pip => spec.split(',')
+                .filter_map(|c| {
+                    let c = c.trim();
+                    if let Some(version) = c.strip_prefix("~=") {
+                        Some(compatible_release(version.trim()))
+                    } else if let Some(version) = c.strip_prefix("==") {
+                        Some(version.trim().replace(".*", ".x"))
+                    } else if c.starts_with("!=") {
+                        None
+                    } else {
+                        Some(c.to_string())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            Self::Composer => spec.split("||")
+                .flat_map(|alternative| alternative.split('|'))
+                .map(|alternative| {
+                    alternative.split([',', ' '])
+                        .filter(|c| !c.is_empty())
+                        .map(|c| match c.strip_prefix('~') {
+                            Some(version) => compatible_release(version),
+                            None => c.replace(".*", ".x"),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join(" || "),
+            Self::Gem => spec.split(',')
+                .filter_map(|c| {
+                    let c = c.trim();
+                    if let Some(version) = c.strip_prefix("~>") {
+                        Some(compatible_release(version.trim()))
+                    } else if c.starts_with("!=") {
+                        None
+                    } else {
+                        Some(c.trim_start_matches('=').trim().to_string())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            // Cargo treats a bare version as a caret requirement
+            Self::Cargo => spec.split(',')
+                .map(|c| {
+                    let c = c.trim();
+                    if c.starts_with(|ch: char| ch.is_ascii_digit()) { format!("^{}", c) } else { c.to_string() }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            // go.mod lists minimum versions
+            Self::Go => format!(">={}", spec.trim_start_matches('v').trim_end_matches("+incompatible")),
+            Self::Pub => if spec == "any" { "*".to_string() } else { spec.to_string() },
+        };
+        Range::parse(&converted).ok()
+    }
+
+    /// The commands adding the given `name@spec` requirements to a project.
+    pub fn install_commands(&self, requirements: &[String]) -> Vec<InstallCommand> {
+        let split = |requirement: &String| {
+            let (name, spec) = split_npm(requirement);
+            (name.to_string(), if spec == "*" { None } else { Some(spec.to_string()) })
+        };
+        let command = |program: &str, args: Vec<String>| InstallCommand { program: program.to_string(), args };
+        if requirements.is_empty() {
+            return Vec::new();
+        }
+        let args = |leading: &[&str], separator: &str| {
+            leading.iter().map(|a| a.to_string())
+                .chain(requirements.iter().map(split).map(|(name, spec)| match spec {
+                    Some(spec) => format!("{}{}{}", name, separator, spec),
+                    None => name,
+                }))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            Self::Pip => vec![command("pip", args(&["install"], ""))],
+            Self::Composer => vec![command("composer", args(&["require"], ":"))],
+            Self::Cargo => vec![command("cargo", args(&["add"], "@"))],
+            Self::Go => vec![command("go", args(&["get"], "@"))],
+            Self::Pub => vec![command("dart", args(&["pub", "add"], ":"))],
+            // `bundle add` takes one gem at a time
+            Self::Gem => requirements.iter()
+                .map(split)
+                .map(|(name, spec)| {
+                    let mut args = vec!["add".to_string(), name];
+                    if let Some(spec) = spec {
+                        args.extend(["--version".to_string(), spec]);
+                    }
+                    command("bundle", args)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Ecosystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// `~> 2.1` / `~=2.1` / composer `~2.1` allow `>=2.1 <3`, and `2.1.3` allows `>=2.1.3 <2.2`.
+fn compatible_release(version: &str) -> String {
+    let parts: Vec<&str> = version.split('.').collect();
+    if parts.len() < 2 {
+        return format!(">={}", version);
+    }
+    let mut upper: Vec<String> = parts[..parts.len() - 1].iter().map(|p| p.to_string()).collect();
+    if let Some(last) = upper.last_mut() {
+        *last = (last.parse::<u64>().unwrap_or(0) + 1).to_string();
+    }
+    format!(">={} <{}", version, upper.join("."))
+}
+
+/// `Django[argon2]>=4.2,<5` is `("django", ">=4.2,<5")`.
+fn split_python_requirement(requirement: &str) -> (String, String) {
+    let requirement = requirement.trim();
+    let end = requirement.find(|c: char| !(c.is_ascii_alphanumeric() || "-_.".contains(c))).unwrap_or(requirement.len());
+    let name = normalize_python_name(&requirement[..end]);
+    let rest = requirement[end..].trim();
+    let rest = match rest.strip_prefix('[') {
+        Some(extras) => extras.split_once(']').map_or("", |(_, r)| r).trim(),
+        None => rest,
+    };
+    let spec = rest.replace(' ', "");
+    (name, if spec.is_empty() { "*".to_string() } else { spec })
+}
+
+/// Python distribution names compare case-insensitively, with `_` and `.` as `-`.
+fn normalize_python_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+/// A version from a TOML dependency entry: `"1.0"` or `{ version = "1.0" }`.
+fn toml_spec(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(spec) => spec.clone(),
+        toml::Value::Table(table) => table.get("version").and_then(|v| v.as_str()).unwrap_or("*").to_string(),
+        _ => "*".to_string(),
+    }
+}
+
+fn first_quoted(text: &str) -> Option<String> {
+    let text = text.trim();
+    let quote = text.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    text[1..].split(quote).next().map(str::to_string)
+}
+
+fn go_module_path(dir: &Path) -> Option<String> {
+    fs::read_to_string(dir.join("go.mod")).ok()?
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module ").map(|m| m.trim().to_string()))
+}
+
+fn pubspec_name(dir: &Path) -> Option<String> {
+    fs::read_to_string(dir.join("pubspec.yaml")).ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("name:").map(|n| n.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_python_imports() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join("app").join("widgets")).unwrap();
+        fs::write(project.join("requirements.txt"), "Django>=4.2,<5\nPyYAML==6.0.1  # config\nrequests\n").unwrap();
+        let file = project.join("app").join("widgets").join("chart.py");
+        fs::write(project.join("app").join("widgets").join("helpers.py"), "").unwrap();
+        let source = "import os, json\nfrom django.utils.html import format_html\nimport yaml\nfrom helpers import fmt\nimport numpy as np\n";
+
+        assert_eq!(
+            Ecosystem::Pip.detect(&file, source),
+            ["django@>=4.2,<5", "pyyaml@==6.0.1", "numpy@*"]
+        );
+    }
+
+    #[test]
+    fn test_detect_other_imports() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path();
+        fs::write(project.join("go.mod"), "module example.com/app\n\nrequire (\n\tgithub.com/a-h/templ v0.2.543\n)\n").unwrap();
+        fs::write(project.join("pubspec.yaml"), "name: app\ndependencies:\n  flutter:\n    sdk: flutter\n  http: ^1.1.0\n").unwrap();
+        fs::write(project.join("Gemfile"), "source 'https://rubygems.org'\ngem 'view_component', '~> 3.0'\n").unwrap();
+        fs::write(project.join("composer.json"), r#"{ "require": { "php": "^8.1", "livewire/livewire": "^3.0" } }"#).unwrap();
+        fs::write(project.join("Cargo.toml"), "[dependencies]\nserde = { version = \"1.0\", features = [\"derive\"] }\n").unwrap();
+
+        let go = "package ui\n\nimport (\n\t\"fmt\"\n\t\"github.com/a-h/templ/runtime\"\n\t\"example.com/app/internal\"\n)\n";
+        assert_eq!(Ecosystem::Go.detect(&project.join("card.go"), go), ["github.com/a-h/templ@v0.2.543"]);
+
+        let dart = "import 'package:flutter/material.dart';\nimport 'package:http/http.dart' as http;\nimport 'package:app/theme.dart';\n";
+        assert_eq!(Ecosystem::Pub.detect(&project.join("card.dart"), dart), ["http@^1.1.0"]);
+
+        let ruby = "require 'json'\nrequire \"view_component\"\n";
+        assert_eq!(Ecosystem::Gem.detect(&project.join("card.rb"), ruby), ["view_component@~> 3.0"]);
+
+        let php = "<?php\nnamespace App\\Livewire;\nuse Livewire\\Component;\nuse App\\Models\\User;\n";
+        assert_eq!(Ecosystem::Composer.detect(&project.join("Card.php"), php), ["livewire/livewire@^3.0"]);
+
+        let rust = "use std::fmt;\nuse serde::Serialize;\nuse crate::theme;\n";
+        assert_eq!(Ecosystem::Cargo.detect(&project.join("card.rs"), rust), ["serde@1.0"]);
+    }
+
+    #[test]
+    fn test_check_and_install() {
+        let declared: BTreeMap<String, String> = [
+            ("rails".to_string(), "~> 7.0".to_string()),
+            ("view_component".to_string(), "~> 2.8".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(Ecosystem::Gem.check(&declared, "rails@>= 7.0.4").status, RequirementStatus::Mismatch("~> 7.0".to_string()));
+        assert_eq!(Ecosystem::Gem.check(&declared, "rails@~> 7.0").status, RequirementStatus::Satisfied("~> 7.0".to_string()));
+        assert_eq!(Ecosystem::Gem.check(&declared, "view_component@~> 3.0").status, RequirementStatus::Mismatch("~> 2.8".to_string()));
+        assert_eq!(Ecosystem::Gem.check(&declared, "turbo-rails@*").status, RequirementStatus::Missing);
+        assert!(Ecosystem::Pip.to_range("~=2.1").unwrap().satisfies(&crate::semver::Version::new(2, 9, 0)));
+        assert!(Ecosystem::Composer.to_range("^1.0 || ~2.1").unwrap().satisfies(&crate::semver::Version::new(2, 5, 0)));
+
+        let install = |ecosystem: Ecosystem, requirements: &[&str]| {
+            let requirements: Vec<String> = requirements.iter().map(|r| r.to_string()).collect();
+            ecosystem.install_commands(&requirements).iter().map(ToString::to_string).collect::<Vec<_>>()
+        };
+        assert_eq!(install(Ecosystem::Pip, &["django@>=4.2,<5", "numpy@*"]), ["pip install 'django>=4.2,<5' numpy"]);
+        assert_eq!(install(Ecosystem::Composer, &["livewire/livewire@^3.0"]), ["composer require livewire/livewire:^3.0"]);
+        assert_eq!(install(Ecosystem::Gem, &["rails@~> 7.0", "turbo-rails@*"]), ["bundle add rails --version '~> 7.0'", "bundle add turbo-rails"]);
+        assert_eq!(install(Ecosystem::Cargo, &["serde@1.0"]), ["cargo add serde@1.0"]);
+        assert_eq!(install(Ecosystem::Go, &["github.com/a-h/templ@v0.2.543"]), ["go get github.com/a-h/templ@v0.2.543"]);
+        assert_eq!(install(Ecosystem::Pub, &["http@^1.1.0"]), ["dart pub add http:^1.1.0"]);
+    }
+}
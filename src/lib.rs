@@ -1,5 +1,18 @@
 //! Component Manager - A CLI tool for managing frontend components
 
+pub mod answers;
 pub mod commands;
 pub mod config;
+pub mod ecosystem;
+pub mod library;
+pub mod lockfile;
+pub mod npm;
+pub mod package_manager;
+pub mod registry;
+pub mod rename;
+pub mod secrets;
+pub mod semver;
+pub mod source;
+pub mod stack;
+pub mod taxonomy;
 pub mod utils;
@@ -0,0 +1,245 @@
+//! Read access to the component library on disk.
+//!
+//! Components live at `<components_dir>/<framework>/<style>/<name>/`, next to
+//! their `component.toml`. Files placed directly in a style directory are
+//! treated as single-file components named after the file.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+
+use crate::commands::dependencies::split_internal;
+use crate::commands::export::ComponentMetadata;
+
+#[derive(Debug, Clone)]
+pub struct LibraryComponent {
+    pub name: String,
+    pub framework: String,
+    pub style: String,
+    /// The component directory, or the file itself for single-file components
+    pub path: PathBuf,
+    /// Source files of the component, without `component.toml`
+    pub files: Vec<PathBuf>,
+}
+
+impl LibraryComponent {
+    /// `framework/style/name`, unique within the library
+    pub fn id(&self) -> String {
+        format!("{}/{}/{}", self.framework, self.style, self.name)
+    }
+
+    /// Whether `query` names this component, either by bare name, by
+    /// `framework/style/name` (optionally prefixed with the library path) or by
+    /// the name of its file.
+    pub fn matches(&self, query: &str) -> bool {
+        query == self.name
+            || query == self.id()
+            || query.ends_with(&format!("/{}", self.id()))
+            || self.files.iter().any(|f| f.file_name().and_then(|n| n.to_str()) == Some(query))
+    }
+
+    pub fn metadata_path(&self) -> PathBuf {
+        self.path.join("component.toml")
+    }
+
+    /// Parses the component's `component.toml`, if it has a valid one.
+    pub fn metadata(&self) -> Option<ComponentMetadata> {
+        let content = fs::read_to_string(self.metadata_path()).ok()?;
+        toml::from_str(&content).ok()
+    }
+}
+
+impl fmt::Display for LibraryComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+/// Finds the component an internal dependency of `dependent` refers to,
+/// preferring one with the same framework and style. A version range on the
+/// reference (`Icon@^1.2.0`) is ignored.
+pub fn resolve_reference<'a>(
+    components: &'a [LibraryComponent],
+    reference: &str,
+    dependent: &LibraryComponent,
+) -> Option<&'a LibraryComponent> {
+    let reference = split_internal(reference).0;
+    let candidates: Vec<&LibraryComponent> = components.iter().filter(|c| c.matches(reference)).collect();
+    candidates.iter()
+        .find(|c| c.framework == dependent.framework && c.style == dependent.style)
+        .or_else(|| candidates.iter().find(|c| c.framework == dependent.framework))
+        .or_else(|| candidates.first())
+        .copied()
+}
+
+/// An internal dependency found by `walk_dependencies`.
+#[derive(Debug, Clone)]
+pub struct InternalEdge<'a> {
+    pub dependent: &'a LibraryComponent,
+    /// The reference as written, e.g. `Icon@^1.2.0`
+    pub reference: String,
+    /// The component it resolves to, `None` when it is not in the library
+    pub dependency: Option<&'a LibraryComponent>,
+}
+
+/// What `walk_dependencies` reached from its roots.
+#[derive(Debug, Default)]
+pub struct DependencyWalk<'a> {
+    /// The roots followed by the components they depend on, directly or not,
+    /// each listed once in the order reached
+    pub components: Vec<&'a LibraryComponent>,
+    pub edges: Vec<InternalEdge<'a>>,
+}
+
+impl<'a> DependencyWalk<'a> {
+    /// The internal dependencies that are not in the library. Whether they
+    /// are worth a warning or an error is up to the caller.
+    pub fn missing(&self) -> impl Iterator<Item = &InternalEdge<'a>> {
+        self.edges.iter().filter(|edge| edge.dependency.is_none())
+    }
+}
+
+/// Follows the internal dependencies of `roots` through the library. The
+/// dependencies of a component reached are only followed when `follow`
+/// accepts it; those of the roots always are.
+pub fn walk_dependencies<'a>(
+    components: &'a [LibraryComponent],
+    roots: impl IntoIterator<Item = &'a LibraryComponent>,
+    follow: impl Fn(&LibraryComponent) -> bool,
+) -> DependencyWalk<'a> {
+    let mut walk = DependencyWalk::default();
+    for root in roots {
+        if !walk.components.iter().any(|c| c.id() == root.id()) {
+            walk.components.push(root);
+        }
+    }
+    let roots = walk.components.len();
+    let mut index = 0;
+    while index < walk.components.len() {
+        let dependent = walk.components[index];
+        index += 1;
+        if index > roots && !follow(dependent) {
+            continue;
+        }
+        let Some(metadata) = dependent.metadata() else {
+            continue;
+        };
+        for reference in metadata.dependencies.dependencies.iter().filter_map(|d| d.internal_reference()) {
+            let dependency = resolve_reference(components, reference, dependent);
+            if let Some(dependency) = dependency
+                && !walk.components.iter().any(|c| c.id() == dependency.id())
+            {
+                walk.components.push(dependency);
+            }
+            walk.edges.push(InternalEdge { dependent, reference: reference.to_string(), dependency });
+        }
+    }
+    walk
+}
+
+/// Looks up the component `query` names, failing when none or several do.
+pub fn find<'a>(components: &'a [LibraryComponent], query: &str) -> Result<&'a LibraryComponent> {
+    let matches: Vec<&LibraryComponent> = components.iter().filter(|c| c.matches(query)).collect();
+    match matches.as_slice() {
+        [] => bail!("Component '{}' not found in the library", query),
+        [component] => Ok(component),
+        _ => bail!(
+            "Component '{}' is ambiguous, use one of: {}",
+            query,
+            matches.iter().map(|c| c.id()).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Lists every component in the library, sorted by id.
+pub fn scan(components_dir: &Path) -> Vec<LibraryComponent> {
+    let mut components = Vec::new();
+    for framework_dir in subdirectories(components_dir) {
+        for style_dir in subdirectories(&framework_dir) {
+            let framework = file_name(&framework_dir);
+            let style = file_name(&style_dir);
+            let Ok(entries) = fs::read_dir(&style_dir) else {
+                continue;
+            };
+            let mut entries: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+            entries.sort();
+            for path in entries {
+                if path.is_dir() {
+                    let files = component_files(&path);
+                    components.push(LibraryComponent {
+                        name: file_name(&path),
+                        framework: framework.clone(),
+                        style: style.clone(),
+                        path,
+                        files,
+                    });
+                } else if path.is_file() {
+                    components.push(LibraryComponent {
+                        name: path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string(),
+                        framework: framework.clone(),
+                        style: style.clone(),
+                        files: vec![path.clone()],
+                        path,
+                    });
+                }
+            }
+        }
+    }
+    components.sort_by_key(|c| c.id());
+    components
+}
+
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn component_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() != "component.toml")
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::fs;
+    use std::path::Path;
+
+    /// Writes a `vue/tailwind` component under `root` that depends on vue and
+    /// on the `internal` references.
+    pub(crate) fn component(root: &Path, name: &str, internal: &[&str]) {
+        let dir = root.join("vue").join("tailwind").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        let dependencies: Vec<String> = internal.iter().map(|d| format!("{{ Internal = \"{}\" }}", d))
+            .chain(std::iter::once("{ Npm = \"vue@^3.0.0\" }".to_string()))
+            .collect();
+        fs::write(
+            dir.join("component.toml"),
+            format!(
+                "name = \"{name}\"\nversion = \"1.2.0\"\nframework = \"vue\"\nstyle = \"tailwind\"\nlanguage = \"typescript\"\n\
+                 description = \"\"\nauthor = \"\"\ncreated_at = \"\"\nupdated_at = \"\"\ntags = []\n\n\
+                 [dependencies]\ndependencies = [{}]\n",
+                dependencies.join(", ")
+            ),
+        )
+        .unwrap();
+    }
+}
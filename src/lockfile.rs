@@ -0,0 +1,60 @@
+//! The project lockfile, `.component-manager.lock`.
+//!
+//! Records which library component each imported copy came from, so that
+//! renamed copies can still be mapped back to their source.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const LOCKFILE_NAME: &str = ".component-manager.lock";
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Lockfile {
+    #[serde(default, rename = "component")]
+    pub components: Vec<LockedComponent>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LockedComponent {
+    /// Name of the component in this project
+    pub name: String,
+    /// `framework/style/name` of the library component it was imported from
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Imported files, relative to the project root
+    pub files: Vec<String>,
+}
+
+impl Lockfile {
+    /// Loads the lockfile from `dir`, or an empty one if there is none.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(LOCKFILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(LOCKFILE_NAME);
+        let content = toml::to_string_pretty(self).context("Failed to serialize lockfile")?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Adds an entry, replacing any previous entry with the same project name.
+    pub fn upsert(&mut self, component: LockedComponent) {
+        self.components.retain(|c| c.name != component.name);
+        self.components.push(component);
+        self.components.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    pub fn find(&self, name: &str) -> Option<&LockedComponent> {
+        self.components.iter().find(|c| c.name == name)
+    }
+}
@@ -1,14 +1,19 @@
-mod commands;
-mod config;
-mod utils;
+use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
-use commands::{
-    export::export_component, 
-    import::import_component, 
-    init::init_config, 
+use component_manager::answers;
+use component_manager::commands::{
+    export::{export_component, export_components_by_glob},
+    import::{import_components, ImportOptions},
+    init::{init_config, InitOptions}, 
     show::show_components,
-    install::install_dependencies,
+    install::{install_dependencies, InstallOptions},
+    graph::show_graph,
+    dependents::show_dependents,
+    deps::deps_outdated,
+    lint::lint_components,
+    fsck::fsck,
+    audit::audit_components,
 };
 
 #[derive(Parser)]
@@ -16,16 +21,53 @@ use commands::{
 struct Cli {
 	#[command(subcommand)]
 	command: Commands,
+
+    /// Answer the interactive prompts from a TOML file instead of asking
+    #[arg(long, global = true, value_name = "FILE")]
+    answers: Option<PathBuf>,
+
+    /// Record the answers given to the interactive prompts into a TOML file
+    #[arg(long, global = true, value_name = "FILE")]
+    record: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Export a component to the component library
-    Export,
-    /// Import a component from the component library
-    Import,
+    Export {
+        /// Export every file matching a glob pattern (e.g. 'src/components/**/*.vue')
+        #[arg(long, value_name = "PATTERN")]
+        glob: Option<String>,
+        /// Export even when the secret scan finds credentials or internal references
+        #[arg(long)]
+        allow_secrets: bool,
+    },
+    /// Import components from the component library
+    Import {
+        /// Components to import, by name or as framework/style/name (default: pick interactively)
+        components: Vec<String>,
+        /// Directory to import the components into
+        #[arg(long, value_name = "DIR")]
+        to: Option<PathBuf>,
+        /// Import a single component under a new name (e.g. AdminButton)
+        #[arg(long = "as", value_name = "NAME")]
+        rename: Option<String>,
+    },
     /// Initialize component manager configuration
-    Init,
+    Init {
+        /// Use the stack detected from the project without prompting
+        #[arg(long)]
+        auto: bool,
+        /// Start from a built-in or user-defined preset, e.g. nuxt-tailwind-ts
+        #[arg(long, conflicts_with = "auto")]
+        preset: Option<String>,
+        /// Write changes to an existing config without asking
+        #[arg(short, long)]
+        yes: bool,
+        /// Add a starter README to the components directory
+        #[arg(long)]
+        readme: bool,
+    },
     /// List available components
     Show {
         /// Show all components, regardless of project configuration
@@ -34,23 +76,166 @@ enum Commands {
     },
     /// Install dependencies for components
     Install {
-        /// Specific component to install dependencies for (default: all components)
+        /// Component to install dependencies for, by name or as framework/style/name (default: all components)
+        component: Option<String>,
+        /// Install without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+        /// Only print the install commands
+        #[arg(long)]
+        dry_run: bool,
+        /// Add the dependencies to package.json instead of running the package manager
+        #[arg(long)]
+        write_manifest: bool,
+    },
+    /// Show the dependency graph between components
+    Graph {
+        /// Only show what this component depends on (default: the whole library)
+        component: Option<String>,
+        /// Output format: ascii, dot or mermaid
+        #[arg(long, default_value = "ascii")]
+        format: String,
+        /// Include package dependencies as leaf nodes
+        #[arg(long)]
+        packages: bool,
+    },
+    /// List the components that depend on a component
+    Dependents {
+        /// Component to look up, by name or as framework/style/name
+        component: String,
+        /// Also look for imported copies in the configured projects
+        #[arg(long)]
+        projects: bool,
+    },
+    /// Maintain the dependencies of library components
+    Deps {
+        #[command(subcommand)]
+        command: DepsCommand,
+    },
+    /// Check component files and metadata against the lint rules
+    Lint {
+        /// Component to check, by name or as framework/style/name (default: all components)
         component: Option<String>,
+        /// Fix the problems that can be fixed automatically
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Check the component library for broken or inconsistent folders
+    Fsck {
+        /// Offer to fix the problems found
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Scan component source for risky patterns such as v-html or eval
+    Audit {
+        /// Component to scan, by name or as framework/style/name (default: all components)
+        component: Option<String>,
+        /// Record the current findings of the component as accepted in its component.toml
+        #[arg(long, requires = "component")]
+        accept: bool,
+        /// Why the findings are acceptable, recorded with --accept
+        #[arg(long, value_name = "TEXT", requires = "accept")]
+        reason: Option<String>,
+        /// Accept the findings without asking for confirmation
+        #[arg(short, long, requires = "accept")]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DepsCommand {
+    /// List npm dependencies whose ranges exclude the latest major version
+    Outdated {
+        /// npm registry URL, or a directory mirroring one (default: the configured registry)
+        #[arg(long, value_name = "URL")]
+        registry: Option<String>,
+        /// Bump the outdated ranges without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 }
 
 fn main() {
 	let cli = Cli::parse();
+
+    if let Err(e) = setup_answers(&cli) {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+
 	match cli.command {
-		Commands::Export => export_component(),
-		Commands::Import => import_component(),
-		Commands::Init => init_config(),
+		Commands::Export { glob: None, allow_secrets } => export_component(allow_secrets),
+        Commands::Export { glob: Some(pattern), allow_secrets } => {
+            if let Err(e) = export_components_by_glob(&pattern, allow_secrets) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Import { components, to, rename } => {
+            if let Err(e) = import_components(&components, &ImportOptions { to, rename }) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+		Commands::Init { auto, preset, yes, readme } => {
+			if let Err(e) = init_config(&InitOptions { auto, preset, yes, readme }) {
+				eprintln!("Error: {:#}", e);
+				std::process::exit(1);
+			}
+		}
         Commands::Show { all } => show_components(all),
-        Commands::Install { component } => {
-            if let Err(e) = install_dependencies(component.as_deref()) {
-                eprintln!("Error: {}", e);
+        Commands::Install { component, yes, dry_run, write_manifest } => {
+            let options = InstallOptions { yes, dry_run, write_manifest };
+            if let Err(e) = install_dependencies(component.as_deref(), &options) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Graph { component, format, packages } => {
+            if let Err(e) = show_graph(component.as_deref(), &format, packages) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Dependents { component, projects } => {
+            if let Err(e) = show_dependents(&component, projects) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Deps { command: DepsCommand::Outdated { registry, yes } } => {
+            if let Err(e) = deps_outdated(registry.as_deref(), yes) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Lint { component, fix } => {
+            if let Err(e) = lint_components(component.as_deref(), fix) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Fsck { repair } => {
+            if let Err(e) = fsck(repair) {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Audit { component, accept, reason, yes } => {
+            if let Err(e) = audit_components(component.as_deref(), accept, reason.as_deref(), yes) {
+                eprintln!("Error: {:#}", e);
                 std::process::exit(1);
             }
         }
 	}
 }
+
+fn setup_answers(cli: &Cli) -> anyhow::Result<()> {
+    if let Some(path) = &cli.answers {
+        answers::replay_from(path)?;
+    }
+    if let Some(path) = &cli.record {
+        answers::record_to(path)?;
+    }
+    Ok(())
+}
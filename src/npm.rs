@@ -0,0 +1,495 @@
+//! The npm packages a consuming project already declares and has installed.
+//!
+//! Declared ranges come from `package.json`. Installed versions come from
+//! `node_modules` when present, otherwise from whichever lockfile the project
+//! has (`package-lock.json`, `yarn.lock` or `pnpm-lock.yaml`).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::commands::dependencies::split_npm;
+use anyhow::{bail, Context, Result};
+
+use crate::semver::{parse_version, Range};
+
+/// `package.json` sections, in the order a declaration is looked up.
+pub const DEPENDENCY_SECTIONS: &[&str] = &["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declared {
+    /// The `package.json` section, e.g. `devDependencies`
+    pub section: String,
+    pub range: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ProjectPackages {
+    pub declared: BTreeMap<String, Declared>,
+    pub installed: BTreeMap<String, String>,
+}
+
+/// How a component's requirement compares with what the project has.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequirementStatus {
+    /// The project has a matching version (the installed version, or the declared range)
+    Satisfied(String),
+    /// The project has the package, but at a version outside the required range
+    Mismatch(String),
+    /// The project does not depend on the package
+    Missing,
+}
+
+#[derive(Debug, Clone)]
+pub struct RequirementCheck {
+    pub name: String,
+    pub range: String,
+    pub status: RequirementStatus,
+}
+
+impl ProjectPackages {
+    /// Reads the packages of the project rooted at `dir`. Missing or
+    /// unreadable files are treated as declaring nothing.
+    pub fn load(dir: &Path) -> Self {
+        let mut packages = Self::default();
+        if let Some(manifest) = read_json(&dir.join("package.json")) {
+            for section in DEPENDENCY_SECTIONS {
+                for (name, range) in manifest.get(section).and_then(serde_json::Value::as_object).into_iter().flatten() {
+                    if let Some(range) = range.as_str() {
+                        packages.declared.entry(name.clone()).or_insert_with(|| Declared {
+                            section: section.to_string(),
+                            range: range.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut installed = BTreeMap::new();
+        if let Ok(content) = fs::read_to_string(dir.join("package-lock.json")) {
+            installed.extend(package_lock_versions(&content));
+        } else if let Ok(content) = fs::read_to_string(dir.join("yarn.lock")) {
+            installed.extend(yarn_lock_versions(&content));
+        } else if let Ok(content) = fs::read_to_string(dir.join("pnpm-lock.yaml")) {
+            installed.extend(pnpm_lock_versions(&content));
+        }
+        // What is actually on disk wins over the lockfile
+        for name in packages.declared.keys() {
+            let manifest = dir.join("node_modules").join(name).join("package.json");
+            if let Some(version) = read_json(&manifest).and_then(|m| m.get("version").and_then(|v| v.as_str()).map(str::to_string)) {
+                installed.insert(name.clone(), version);
+            }
+        }
+        packages.installed = installed;
+        packages
+    }
+
+    /// Checks a requirement such as `vue@^3.0.0` against the project.
+    pub fn check(&self, requirement: &str) -> RequirementCheck {
+        let (name, range) = split_npm(requirement);
+        let status = match self.declared.get(name) {
+            None => RequirementStatus::Missing,
+            Some(declared) => {
+                let required = Range::parse(range).ok();
+                match (required, self.installed.get(name)) {
+                    // Tags such as `latest` accept whatever the project has
+                    (None, Some(version)) => RequirementStatus::Satisfied(version.clone()),
+                    (None, None) => RequirementStatus::Satisfied(declared.range.clone()),
+                    (Some(required), Some(version)) => match parse_version(version) {
+                        Ok(parsed) if !required.satisfies(&parsed) => RequirementStatus::Mismatch(version.clone()),
+                        _ => RequirementStatus::Satisfied(version.clone()),
+                    },
+                    // Not installed yet: judge by the lowest version the declared range allows
+                    (Some(required), None) => match Range::parse(&declared.range).ok().and_then(|r| r.min_version()) {
+                        Some(lowest) if !required.satisfies(&lowest) => RequirementStatus::Mismatch(declared.range.clone()),
+                        _ => RequirementStatus::Satisfied(declared.range.clone()),
+                    },
+                }
+            }
+        };
+        RequirementCheck { name: name.to_string(), range: range.to_string(), status }
+    }
+}
+
+/// A dependency to declare in `package.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// `dependencies`, `devDependencies` or `peerDependencies`
+    pub section: String,
+    pub name: String,
+    pub range: String,
+}
+
+/// An entry `merge_into_manifest` added, or changed from `previous`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestChange {
+    pub entry: ManifestEntry,
+    pub previous: Option<String>,
+}
+
+/// Merges `entries` into the text of a `package.json`. Existing entries are
+/// updated in place; new ones are inserted in alphabetical order when the
+/// section is sorted and appended otherwise, following the indentation and
+/// spacing of their neighbours, so the rest of the file is left untouched.
+pub fn merge_into_manifest(content: &str, entries: &[ManifestEntry]) -> Result<(String, Vec<ManifestChange>)> {
+    let manifest: serde_json::Value = serde_json::from_str(content).context("package.json is not valid JSON")?;
+    if !manifest.is_object() {
+        bail!("package.json does not contain a JSON object");
+    }
+    let mut content = content.to_string();
+    let mut changes = Vec::new();
+    for entry in entries {
+        if manifest.get(&entry.section).is_some_and(|section| !section.is_object()) {
+            bail!("`{}` in package.json is not an object", entry.section);
+        }
+        let root = locate_object(&content, &[]).context("package.json does not contain a JSON object")?;
+        let colon = root.entries.first().map_or(": ".to_string(), |e| content[e.key.end..e.value.start].to_string());
+        let unit = root.entries.first()
+            .map(|e| line_indent(&content, e.key.start).to_string())
+            .filter(|indent| !indent.is_empty())
+            .unwrap_or_else(|| "  ".to_string());
+        let line = format!("{}{}{}", quote(&entry.name), colon, quote(&entry.range));
+
+        let Some(section) = locate_object(&content, &[entry.section.as_str()]) else {
+            // A new section, at the end of the root object
+            let outer = root.entries.last().map_or("", |e| line_indent(&content, e.key.start)).to_string();
+            let object = if content.contains('\n') {
+                format!("{{\n{}{}{}\n{}}}", outer, unit, line, outer)
+            } else {
+                // Single-line manifests stay on one line
+                let separator = root.entries.last().map_or("", |e| whitespace_before(&content, e.key.start));
+                format!("{{{}{}{}}}", separator, line, separator)
+            };
+            let section_line = format!("{}{}{}", quote(&entry.section), colon, object);
+            match root.entries.last() {
+                Some(last) => {
+                    let separator = whitespace_before(&content, last.key.start).to_string();
+                    content.insert_str(last.value.end, &format!(",{}{}", separator, section_line));
+                }
+                None => content.replace_range(root.range.clone(), &format!("{{\n{}{}\n}}", unit, section_line)),
+            }
+            changes.push(ManifestChange { entry: entry.clone(), previous: None });
+            continue;
+        };
+
+        if let Some(existing) = section.entries.iter().find(|e| e.name == entry.name) {
+            let previous = serde_json::from_str::<serde_json::Value>(&content[existing.value.clone()])?
+                .as_str()
+                .map(str::to_string);
+            if previous.as_deref() != Some(entry.range.as_str()) {
+                content.replace_range(existing.value.clone(), &quote(&entry.range));
+                changes.push(ManifestChange { entry: entry.clone(), previous });
+            }
+            continue;
+        }
+
+        let sorted = section.entries.windows(2).all(|pair| pair[0].name <= pair[1].name);
+        let next = section.entries.iter().find(|e| sorted && e.name > entry.name);
+        match (next, section.entries.last()) {
+            (Some(next), _) => {
+                let separator = whitespace_before(&content, next.key.start).to_string();
+                content.insert_str(next.key.start, &format!("{},{}", line, separator));
+            }
+            (None, Some(last)) => {
+                let separator = whitespace_before(&content, last.key.start).to_string();
+                content.insert_str(last.value.end, &format!(",{}{}", separator, line));
+            }
+            (None, None) if content.contains('\n') => {
+                let outer = line_indent(&content, section.range.start).to_string();
+                content.replace_range(section.range.clone(), &format!("{{\n{}{}{}\n{}}}", outer, unit, line, outer));
+            }
+            (None, None) => content.replace_range(section.range.clone(), &format!("{{ {} }}", line)),
+        }
+        changes.push(ManifestChange { entry: entry.clone(), previous: None });
+    }
+    Ok((content, changes))
+}
+
+/// Where an object and its entries sit in the text of a manifest, so it can
+/// be edited without reformatting the rest.
+struct ObjectSpan {
+    /// From the opening `{` to just past the closing `}`
+    range: std::ops::Range<usize>,
+    entries: Vec<EntrySpan>,
+}
+
+struct EntrySpan {
+    name: String,
+    /// From the key's opening quote to just past its closing quote
+    key: std::ops::Range<usize>,
+    value: std::ops::Range<usize>,
+}
+
+/// Locates the object reached by following `path` from the root object of
+/// `content`, which must be valid JSON. `None` when a key is missing or the
+/// value found is not an object.
+fn locate_object(content: &str, path: &[&str]) -> Option<ObjectSpan> {
+    let bytes = content.as_bytes();
+    let mut span = object_span(content, skip_whitespace(bytes, 0))?;
+    for key in path {
+        let entry = span.entries.iter().find(|e| e.name == *key)?;
+        span = object_span(content, entry.value.start)?;
+    }
+    Some(span)
+}
+
+fn object_span(content: &str, start: usize) -> Option<ObjectSpan> {
+    let bytes = content.as_bytes();
+    if bytes.get(start) != Some(&b'{') {
+        return None;
+    }
+    let mut entries = Vec::new();
+    let mut position = skip_whitespace(bytes, start + 1);
+    while bytes.get(position).is_some_and(|b| *b != b'}') {
+        let key = position..value_end(bytes, position);
+        let name = serde_json::from_str(&content[key.clone()]).ok()?;
+        // Past the `:`
+        let value_start = skip_whitespace(bytes, skip_whitespace(bytes, key.end) + 1);
+        let value = value_start..value_end(bytes, value_start);
+        position = skip_whitespace(bytes, value.end);
+        if bytes.get(position) == Some(&b',') {
+            position = skip_whitespace(bytes, position + 1);
+        }
+        entries.push(EntrySpan { name, key, value });
+    }
+    Some(ObjectSpan { range: start..position + 1, entries })
+}
+
+/// Just past the JSON value starting at `start`.
+fn value_end(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (position, byte) in bytes.iter().enumerate().skip(start) {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    if depth == 0 {
+                        return position + 1;
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            // The end of a number or literal
+            b'}' | b']' | b',' if depth == 0 => return position,
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return position + 1;
+                }
+            }
+            byte if depth == 0 && byte.is_ascii_whitespace() => return position,
+            _ => {}
+        }
+    }
+    bytes.len()
+}
+
+fn skip_whitespace(bytes: &[u8], start: usize) -> usize {
+    start + bytes.iter().skip(start).take_while(|b| b.is_ascii_whitespace()).count()
+}
+
+fn quote(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
+/// The indentation of the line `position` is on.
+fn line_indent(content: &str, position: usize) -> &str {
+    let line_start = content[..position].rfind('\n').map_or(0, |i| i + 1);
+    let line = &content[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// The whitespace right before `position`.
+fn whitespace_before(content: &str, position: usize) -> &str {
+    let before = &content[..position];
+    &before[before.trim_end().len()..]
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Versions of the top-level packages in a `package-lock.json` (v1 to v3).
+fn package_lock_versions(content: &str) -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::new();
+    let Ok(lock) = serde_json::from_str::<serde_json::Value>(content) else {
+        return versions;
+    };
+    for (path, entry) in lock.get("packages").and_then(serde_json::Value::as_object).into_iter().flatten() {
+        // Nested `node_modules` are dependencies of dependencies
+        if let Some(name) = path.strip_prefix("node_modules/").filter(|n| !n.contains("/node_modules/"))
+            && let Some(version) = entry.get("version").and_then(serde_json::Value::as_str)
+        {
+            versions.insert(name.to_string(), version.to_string());
+        }
+    }
+    for (name, entry) in lock.get("dependencies").and_then(serde_json::Value::as_object).into_iter().flatten() {
+        if let Some(version) = entry.get("version").and_then(serde_json::Value::as_str) {
+            versions.entry(name.clone()).or_insert_with(|| version.to_string());
+        }
+    }
+    versions
+}
+
+/// Versions from a classic or Berry `yarn.lock`.
+fn yarn_lock_versions(content: &str) -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(' ') {
+            // `"@scope/pkg@^1.0.0", "@scope/pkg@npm:^1.1.0":`
+            current = line.trim_end_matches(':')
+                .split(',')
+                .next()
+                .map(|spec| split_npm(spec.trim().trim_matches('"')).0.to_string());
+        } else if let Some(name) = &current
+            && let Some(version) = line.trim().strip_prefix("version")
+        {
+            let version = version.trim_start_matches(':').trim().trim_matches('"');
+            versions.entry(name.clone()).or_insert_with(|| version.to_string());
+        }
+    }
+    versions
+}
+
+/// Versions of the direct dependencies recorded in a `pnpm-lock.yaml`.
+fn pnpm_lock_versions(content: &str) -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::new();
+    // Indentation of the enclosing `dependencies:` key and of the package being read
+    let mut section: Option<usize> = None;
+    let mut package: Option<(usize, String)> = None;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if section.is_some_and(|s| indent <= s) {
+            section = None;
+        }
+        if package.as_ref().is_some_and(|(p, _)| indent <= *p) {
+            package = None;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim_matches(['\'', '"']);
+        let value = value.trim().trim_matches(['\'', '"']);
+        if matches!(key, "dependencies" | "devDependencies" | "optionalDependencies") {
+            section = Some(indent);
+        } else if section.is_some() && package.is_none() {
+            if value.is_empty() {
+                package = Some((indent, key.to_string()));
+            } else {
+                // lockfile v5: `vue: 3.4.21`
+                versions.entry(key.to_string()).or_insert_with(|| strip_peer_suffix(value));
+            }
+        } else if let Some((_, name)) = &package
+            && key == "version"
+        {
+            versions.entry(name.clone()).or_insert_with(|| strip_peer_suffix(value));
+        }
+    }
+    versions
+}
+
+/// `3.4.21(typescript@5.4.0)` and `3.4.21_typescript@5.4.0` are `3.4.21`.
+fn strip_peer_suffix(version: &str) -> String {
+    version.split(['(', '_']).next().unwrap_or(version).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_requirements() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(
+            dir.join("package.json"),
+            r#"{ "dependencies": { "vue": "^3.2.0", "react": "^17.0.2", "@vueuse/core": "^9.0.0" } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("package-lock.json"),
+            r#"{ "lockfileVersion": 3, "packages": {
+                "": { "name": "app" },
+                "node_modules/vue": { "version": "3.4.21" },
+                "node_modules/react": { "version": "17.0.2" },
+                "node_modules/foo/node_modules/vue": { "version": "2.7.0" }
+            } }"#,
+        )
+        .unwrap();
+
+        let packages = ProjectPackages::load(dir);
+        assert_eq!(packages.check("vue@^3.4.0").status, RequirementStatus::Satisfied("3.4.21".to_string()));
+        assert_eq!(packages.check("react@^18.0.0").status, RequirementStatus::Mismatch("17.0.2".to_string()));
+        assert_eq!(packages.check("@vueuse/core@^10.0.0").status, RequirementStatus::Mismatch("^9.0.0".to_string()));
+        assert_eq!(packages.check("lodash-es@*").status, RequirementStatus::Missing);
+    }
+
+    #[test]
+    fn test_merge_into_manifest() {
+        let entry = |section: &str, name: &str, range: &str| ManifestEntry {
+            section: section.to_string(),
+            name: name.to_string(),
+            range: range.to_string(),
+        };
+        let manifest = "{\n    \"name\": \"app\",\n    \"files\": [\"dist\", \"src\"],\n    \"dependencies\": {\n        \"clsx\": \"^1.2.0\",\n        \"vue\": \"^3.2.0\"\n    },\n    \"devDependencies\": {}\n}\n";
+        let (merged, changes) = merge_into_manifest(
+            manifest,
+            &[
+                entry("dependencies", "@vueuse/core", "^10.0.0"),
+                entry("dependencies", "focus-trap", "^7.0.0"),
+                entry("dependencies", "vue", "^3.4.0"),
+                entry("dependencies", "zod", "^3.0.0"),
+                entry("devDependencies", "@types/node", "^20.0.0"),
+                entry("peerDependencies", "react", "^18.0.0"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            merged,
+            "{\n    \"name\": \"app\",\n    \"files\": [\"dist\", \"src\"],\n    \"dependencies\": {\n        \"@vueuse/core\": \"^10.0.0\",\n        \"clsx\": \"^1.2.0\",\n        \"focus-trap\": \"^7.0.0\",\n        \"vue\": \"^3.4.0\",\n        \"zod\": \"^3.0.0\"\n    },\n    \"devDependencies\": {\n        \"@types/node\": \"^20.0.0\"\n    },\n    \"peerDependencies\": {\n        \"react\": \"^18.0.0\"\n    }\n}\n"
+        );
+        let previous: Vec<Option<&str>> = changes.iter().map(|c| c.previous.as_deref()).collect();
+        assert_eq!(previous, [None, None, Some("^3.2.0"), None, None, None]);
+
+        // Inline objects keep their spacing, and unchanged entries are not reported
+        let (merged, changes) = merge_into_manifest(
+            r#"{"dependencies":{"vue":"^3.4.0", "b": "1"}}"#,
+            &[entry("dependencies", "vue", "^3.4.0"), entry("dependencies", "a", "2")],
+        )
+        .unwrap();
+        assert_eq!(merged, r#"{"dependencies":{"vue":"^3.4.0", "b": "1", "a":"2"}}"#);
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[test]
+    fn test_lockfile_versions() {
+        let yarn = "# yarn lockfile v1\n\n\"@vue/shared@^3.4.0\", \"@vue/shared@^3.4.21\":\n  version \"3.4.21\"\n  resolved \"https://example.com\"\n\nvue@^3.4.0:\n  version \"3.4.21\"\n";
+        let versions = yarn_lock_versions(yarn);
+        assert_eq!(versions.get("@vue/shared").map(String::as_str), Some("3.4.21"));
+        assert_eq!(versions.get("vue").map(String::as_str), Some("3.4.21"));
+
+        let pnpm = "lockfileVersion: '9.0'\n\nimporters:\n\n  .:\n    dependencies:\n      vue:\n        specifier: ^3.4.0\n        version: 3.4.21(typescript@5.4.0)\n    devDependencies:\n      '@types/node':\n        specifier: ^20.0.0\n        version: 20.11.0\n\npackages:\n\n  vue@3.4.21:\n    resolution: {integrity: sha512-x}\n";
+        let versions = pnpm_lock_versions(pnpm);
+        assert_eq!(versions.get("vue").map(String::as_str), Some("3.4.21"));
+        assert_eq!(versions.get("@types/node").map(String::as_str), Some("20.11.0"));
+        assert_eq!(versions.len(), 2);
+    }
+}
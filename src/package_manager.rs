@@ -0,0 +1,220 @@
+//! Choosing the package manager a project installs its dependencies with.
+//!
+//! The configured `package_manager` wins, then the `packageManager` field of
+//! `package.json`, then the lockfile found in the project or one of its
+//! parent directories. Projects without any of those use npm.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::shell_quote;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PackageManager {
+    #[default]
+    Npm,
+    Yarn,
+    Pnpm,
+    Bun,
+}
+
+/// Lockfiles and the package manager that writes them, most specific first.
+const LOCKFILES: &[(&str, PackageManager)] = &[
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("yarn.lock", PackageManager::Yarn),
+    ("bun.lockb", PackageManager::Bun),
+    ("bun.lock", PackageManager::Bun),
+    ("package-lock.json", PackageManager::Npm),
+    ("npm-shrinkwrap.json", PackageManager::Npm),
+];
+
+impl PackageManager {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "npm" => Some(Self::Npm),
+            "yarn" => Some(Self::Yarn),
+            "pnpm" => Some(Self::Pnpm),
+            "bun" => Some(Self::Bun),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Yarn => "yarn",
+            Self::Pnpm => "pnpm",
+            Self::Bun => "bun",
+        }
+    }
+
+    /// Detects the package manager of the project in `project_dir`, unless
+    /// one is `configured`.
+    pub fn detect(project_dir: &Path, configured: Option<&str>) -> Self {
+        if let Some(manager) = configured.and_then(Self::from_name) {
+            return manager;
+        }
+        let Ok(project_dir) = std::path::absolute(project_dir) else {
+            return Self::default();
+        };
+        // `"packageManager": "pnpm@9.1.0"`, in the project or its workspace root
+        for dir in project_dir.ancestors() {
+            if let Some(manager) = read_manifest(dir)
+                .and_then(|m| m.get("packageManager").and_then(|v| v.as_str()).map(str::to_string))
+                .and_then(|field| Self::from_name(field.split('@').next().unwrap_or_default()))
+            {
+                return manager;
+            }
+        }
+        project_dir.ancestors()
+            .find_map(|dir| LOCKFILES.iter().find(|(file, _)| dir.join(file).is_file()).map(|(_, m)| *m))
+            .unwrap_or_default()
+    }
+
+    /// The command adding `packages` to the project, as a regular or a dev
+    /// dependency, optionally targeting one package of a workspace.
+    pub fn add_command(&self, packages: &[String], dev: bool, workspace: Option<&Workspace>) -> InstallCommand {
+        let mut args: Vec<String> = Vec::new();
+        let mut push = |arg: &str| args.push(arg.to_string());
+        match self {
+            Self::Npm => {
+                push("install");
+                push(if dev { "--save-dev" } else { "--save" });
+                if let Some(workspace) = workspace {
+                    push("--workspace");
+                    push(&workspace.package);
+                }
+            }
+            Self::Yarn => {
+                if let Some(workspace) = workspace {
+                    push("workspace");
+                    push(&workspace.package);
+                }
+                push("add");
+                if dev {
+                    push("--dev");
+                }
+            }
+            Self::Pnpm => {
+                push("add");
+                if dev {
+                    push("--save-dev");
+                }
+                if let Some(workspace) = workspace {
+                    push("--filter");
+                    push(&workspace.package);
+                }
+            }
+            Self::Bun => {
+                push("add");
+                if dev {
+                    push("--dev");
+                }
+                if let Some(workspace) = workspace {
+                    push("--cwd");
+                    push(&workspace.package_dir.display().to_string());
+                }
+            }
+        }
+        args.extend(packages.iter().cloned());
+        InstallCommand { program: self.name().to_string(), args }
+    }
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A project that is one package of a monorepo workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    /// The directory declaring the workspace
+    pub root: PathBuf,
+    /// The name of the project's package
+    pub package: String,
+    /// The directory of the project's package
+    pub package_dir: PathBuf,
+}
+
+impl Workspace {
+    /// Finds the workspace `project_dir` belongs to. A workspace root itself
+    /// is not treated as a member.
+    pub fn find(project_dir: &Path) -> Option<Self> {
+        let package_dir = std::path::absolute(project_dir).ok()?;
+        let package = read_manifest(&package_dir)?.get("name")?.as_str()?.to_string();
+        let root = package_dir.ancestors().skip(1).find(|dir| {
+            dir.join("pnpm-workspace.yaml").is_file()
+                || read_manifest(dir).is_some_and(|m| m.get("workspaces").is_some())
+        })?;
+        Some(Self { root: root.to_path_buf(), package, package_dir })
+    }
+}
+
+/// A program and its arguments, displayed as a copy-pasteable shell command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl fmt::Display for InstallCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", shell_quote(arg))?;
+        }
+        Ok(())
+    }
+}
+
+fn read_manifest(dir: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(dir.join("package.json")).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_package_manager() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let app = root.join("apps").join("web");
+        fs::create_dir_all(&app).unwrap();
+        fs::write(app.join("package.json"), r#"{ "name": "@acme/web" }"#).unwrap();
+
+        assert_eq!(PackageManager::detect(&app, None), PackageManager::Npm);
+        fs::write(root.join("yarn.lock"), "").unwrap();
+        assert_eq!(PackageManager::detect(&app, None), PackageManager::Yarn, "lockfiles are found in parents");
+        fs::write(root.join("package.json"), r#"{ "packageManager": "pnpm@9.1.0", "workspaces": ["apps/*"] }"#).unwrap();
+        assert_eq!(PackageManager::detect(&app, None), PackageManager::Pnpm);
+        assert_eq!(PackageManager::detect(&app, Some("bun")), PackageManager::Bun);
+
+        let workspace = Workspace::find(&app).unwrap();
+        assert_eq!(workspace.package, "@acme/web");
+        assert!(Workspace::find(root).is_none(), "the root is not a member");
+    }
+
+    #[test]
+    fn test_add_commands() {
+        let packages = vec!["vue@^3.4.0".to_string(), "@vueuse/core@>=10.0.0 <11.0.0".to_string()];
+        let workspace = Workspace {
+            root: PathBuf::from("/repo"),
+            package: "@acme/web".to_string(),
+            package_dir: PathBuf::from("/repo/apps/web"),
+        };
+        let command = |manager: PackageManager, dev: bool, workspace: Option<&Workspace>| {
+            manager.add_command(&packages, dev, workspace).to_string()
+        };
+
+        assert_eq!(command(PackageManager::Npm, false, None), "npm install --save vue@^3.4.0 '@vueuse/core@>=10.0.0 <11.0.0'");
+        assert_eq!(command(PackageManager::Yarn, true, None), "yarn add --dev vue@^3.4.0 '@vueuse/core@>=10.0.0 <11.0.0'");
+        assert_eq!(command(PackageManager::Pnpm, false, Some(&workspace)), "pnpm add --filter @acme/web vue@^3.4.0 '@vueuse/core@>=10.0.0 <11.0.0'");
+        assert_eq!(command(PackageManager::Yarn, false, Some(&workspace)), "yarn workspace @acme/web add vue@^3.4.0 '@vueuse/core@>=10.0.0 <11.0.0'");
+        assert_eq!(command(PackageManager::Npm, true, Some(&workspace)), "npm install --save-dev --workspace @acme/web vue@^3.4.0 '@vueuse/core@>=10.0.0 <11.0.0'");
+        assert_eq!(command(PackageManager::Bun, true, Some(&workspace)), "bun add --dev --cwd /repo/apps/web vue@^3.4.0 '@vueuse/core@>=10.0.0 <11.0.0'");
+    }
+}
@@ -0,0 +1,94 @@
+//! Looking up package versions in an npm registry.
+//!
+//! The registry is the public one unless configured otherwise, e.g. a local
+//! Verdaccio. A `file://` URL or a plain directory is read as a mirror laid
+//! out like Verdaccio's storage, with `<name>/package.json` holding each
+//! package's metadata.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::semver::{parse_version, Version};
+
+pub const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// How long a registry may take to answer before the lookup fails.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct Registry {
+    url: String,
+}
+
+impl Registry {
+    pub fn new(url: &str) -> Self {
+        Self { url: url.trim_end_matches('/').to_string() }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The directory of a file-based mirror, `None` for HTTP registries.
+    fn mirror_dir(&self) -> Option<PathBuf> {
+        match self.url.strip_prefix("file://") {
+            Some(path) => Some(PathBuf::from(path)),
+            None if !self.url.contains("://") => Some(PathBuf::from(&self.url)),
+            None => None,
+        }
+    }
+
+    /// The metadata the registry has for a package.
+    fn packument(&self, name: &str) -> Result<serde_json::Value> {
+        let content = match self.mirror_dir() {
+            Some(dir) => {
+                let path = dir.join(name).join("package.json");
+                fs::read_to_string(&path).with_context(|| format!("{} is not in the mirror at {}", name, dir.display()))?
+            }
+            None => {
+                // Scoped names keep their `@` but escape the slash
+                let url = format!("{}/{}", self.url, name.replace('/', "%2f"));
+                let agent = ureq::AgentBuilder::new().timeout_connect(Duration::from_secs(10)).timeout(TIMEOUT).build();
+                let response = agent.get(&url)
+                    .set("Accept", "application/vnd.npm.install-v1+json")
+                    .call()
+                    .with_context(|| format!("Failed to fetch {}", url))?;
+                response.into_string().with_context(|| format!("Failed to read {}", url))?
+            }
+        };
+        serde_json::from_str(&content).with_context(|| format!("Invalid registry metadata for {}", name))
+    }
+
+    /// The version the `latest` dist-tag points to.
+    pub fn latest(&self, name: &str) -> Result<Version> {
+        let packument = self.packument(name)?;
+        let Some(latest) = packument.get("dist-tags").and_then(|tags| tags.get("latest")).and_then(|v| v.as_str()) else {
+            bail!("The registry has no latest version of {}", name);
+        };
+        parse_version(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_mirror() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let package_dir = temp_dir.path().join("@vueuse").join("core");
+        fs::create_dir_all(&package_dir).unwrap();
+        fs::write(
+            package_dir.join("package.json"),
+            r#"{ "name": "@vueuse/core", "dist-tags": { "latest": "10.9.0", "next": "11.0.0-beta.1" } }"#,
+        )
+        .unwrap();
+
+        let registry = Registry::new(&format!("file://{}/", temp_dir.path().display()));
+        assert_eq!(registry.latest("@vueuse/core").unwrap(), Version::new(10, 9, 0));
+        assert!(registry.latest("vue").is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! Renaming a component while importing it.
+//!
+//! Files named after the component are renamed, and the component identifier
+//! is rewritten in the source: declarations such as the React export name, the
+//! Vue `name` option, relative imports of sibling files and CSS class names
+//! prefixed with the kebab-case component name (`.button`, `button--primary`).
+
+use crate::source::{is_ident_char, tokenize, Token};
+
+/// A component rename from `from` to `to`, both in PascalCase.
+#[derive(Debug, Clone)]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+}
+
+impl Rename {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self { from: from.to_string(), to: to.to_string() }
+    }
+
+    /// Renames a file named after the component, e.g. `Button.module.css`.
+    pub fn file_name(&self, file_name: &str) -> String {
+        match file_name.strip_prefix(&self.from) {
+            Some(rest) if rest.is_empty() || rest.starts_with('.') => format!("{}{}", self.to, rest),
+            _ => file_name.to_string(),
+        }
+    }
+
+    /// Whether `source` rewrites files with this extension. Other files, such
+    /// as images and fonts, are copied as they are.
+    pub fn rewrites(ext: &str) -> bool {
+        matches!(ext, "vue" | "svelte" | "html" | "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "css" | "scss" | "sass" | "less")
+    }
+
+    /// Rewrites the component identifier in a source file with the given extension.
+    pub fn source(&self, ext: &str, content: &str) -> String {
+        match ext {
+            "vue" | "svelte" | "html" => self.single_file_component(content),
+            "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => self.class_attributes(&self.script(content)),
+            "css" | "scss" | "sass" | "less" => self.stylesheet(content),
+            _ => content.to_string(),
+        }
+    }
+
+    /// Rewrites `<script>` and `<style>` blocks as code and the rest as markup.
+    fn single_file_component(&self, content: &str) -> String {
+        let mut out = String::with_capacity(content.len());
+        let mut rest = content;
+        loop {
+            let next = ["<script", "<style"].iter()
+                .filter_map(|tag| rest.find(tag).map(|i| (i, *tag)))
+                .min_by_key(|(i, _)| *i);
+            let Some((start, tag)) = next else {
+                out.push_str(&self.class_attributes(rest));
+                return out;
+            };
+            let close = format!("</{}>", &tag[1..]);
+            let Some(body_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+                out.push_str(&self.class_attributes(rest));
+                return out;
+            };
+            let body_end = rest[body_start..].find(&close).map(|i| body_start + i).unwrap_or(rest.len());
+
+            out.push_str(&self.class_attributes(&rest[..body_start]));
+            let body = &rest[body_start..body_end];
+            if tag == "<script" {
+                out.push_str(&self.script(body));
+            } else {
+                out.push_str(&self.stylesheet(body));
+            }
+            rest = &rest[body_end..];
+        }
+    }
+
+    /// Renames declared identifiers that are the component name or start with
+    /// it (`ButtonProps`), the Vue `name` option and relative imports of files
+    /// named after the component. Other strings and comments are left alone.
+    fn script(&self, code: &str) -> String {
+        let declared = declared_identifiers(code);
+        let renamed: Vec<&str> = declared.iter()
+            .map(String::as_str)
+            .filter(|ident| self.is_component_identifier(ident))
+            .collect();
+
+        let mut out = String::with_capacity(code.len());
+        for token in tokenize(code) {
+            match token {
+                Token::Code(text) => out.push_str(&replace_identifiers(text, &renamed, &self.from, &self.to)),
+                Token::Str(literal) => {
+                    let quote = &literal[..1];
+                    let inner = &literal[1..literal.len() - 1];
+                    let is_name_option = out.trim_end().ends_with("name:") && inner == self.from;
+                    if is_name_option {
+                        out.push_str(&format!("{quote}{}{quote}", self.to));
+                    } else if let Some(path) = self.relative_import(inner) {
+                        out.push_str(&format!("{quote}{}{quote}", path));
+                    } else {
+                        out.push_str(literal);
+                    }
+                }
+                Token::Comment(text) => out.push_str(text),
+            }
+        }
+        out
+    }
+
+    fn is_component_identifier(&self, ident: &str) -> bool {
+        match ident.strip_prefix(&self.from) {
+            Some(rest) => rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_uppercase()),
+            None => false,
+        }
+    }
+
+    /// `./Button.module.css` becomes `./AdminButton.module.css`.
+    fn relative_import(&self, specifier: &str) -> Option<String> {
+        let (dir, file) = specifier.rsplit_once('/')?;
+        if !specifier.starts_with('.') {
+            return None;
+        }
+        let renamed = self.file_name(file);
+        (renamed != file).then(|| format!("{}/{}", dir, renamed))
+    }
+
+    /// Renames `.button`-prefixed class selectors.
+    fn stylesheet(&self, css: &str) -> String {
+        let from = format!(".{}", kebab_case(&self.from));
+        let to = format!(".{}", kebab_case(&self.to));
+        replace_prefixed(css, &from, &to, |_| true)
+    }
+
+    /// Renames `button`-prefixed classes inside `class`, `className` and
+    /// `:class` attribute values.
+    fn class_attributes(&self, markup: &str) -> String {
+        let from = kebab_case(&self.from);
+        let to = kebab_case(&self.to);
+        let mut out = String::with_capacity(markup.len());
+        let mut rest = markup;
+        while let Some(value_start) = find_class_attribute(rest) {
+            let open = rest[value_start..].chars().next().unwrap();
+            let close = match open {
+                '{' => '}',
+                other => other,
+            };
+            let value_end = rest[value_start + 1..].find(close)
+                .map(|i| value_start + 1 + i + 1)
+                .unwrap_or(rest.len());
+            out.push_str(&rest[..value_start]);
+            out.push_str(&replace_prefixed(&rest[value_start..value_end], &from, &to, |before| {
+                before.is_none_or(|c| !c.is_ascii_alphanumeric() && c != '-' && c != '_')
+            }));
+            rest = &rest[value_end..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Converts `DataTable` to `data-table`.
+pub fn kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !out.ends_with('-') {
+                out.push('-');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else if c == '_' || c == ' ' {
+            out.push('-');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Returns the offset of the opening quote or brace of the next class attribute value.
+fn find_class_attribute(markup: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(i) = markup[offset..].find("class") {
+        let at = offset + i;
+        offset = at + "class".len();
+        let is_attribute_start = markup[..at].chars().last()
+            .is_none_or(|c| c.is_whitespace() || c == ':');
+        if !is_attribute_start {
+            continue;
+        }
+        let after_name = markup[offset..].strip_prefix("Name").unwrap_or(&markup[offset..]);
+        let Some(value) = after_name.trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        if value.starts_with(['"', '\'', '{']) {
+            return Some(markup.len() - value.len());
+        }
+    }
+    None
+}
+
+/// Replaces `from` with `to` wherever it is followed by a non-identifier
+/// character (or `-`/`_`) and `accept_before` approves the preceding character.
+fn replace_prefixed(text: &str, from: &str, to: &str, accept_before: impl Fn(Option<char>) -> bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find(from) {
+        let before = out.chars().last().filter(|_| i == 0).or_else(|| rest[..i].chars().last());
+        let after = rest[i + from.len()..].chars().next();
+        let boundary_after = after.is_none_or(|c| !c.is_ascii_alphanumeric());
+        out.push_str(&rest[..i]);
+        if boundary_after && accept_before(before) {
+            out.push_str(to);
+        } else {
+            out.push_str(from);
+        }
+        rest = &rest[i + from.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Identifiers introduced by a declaration keyword.
+fn declared_identifiers(code: &str) -> Vec<String> {
+    const KEYWORDS: &[&str] = &["function", "class", "const", "let", "var", "interface", "type", "enum"];
+    let mut declared = Vec::new();
+    for token in tokenize(code) {
+        let Token::Code(text) = token else { continue };
+        let words: Vec<&str> = text.split(|c: char| !is_ident_char(c)).filter(|w| !w.is_empty()).collect();
+        for pair in words.windows(2) {
+            if KEYWORDS.contains(&pair[0]) && !declared.iter().any(|d| d == pair[1]) {
+                declared.push(pair[1].to_string());
+            }
+        }
+    }
+    declared
+}
+
+/// Replaces whole-word occurrences of the given identifiers, swapping the
+/// `from` prefix for `to`.
+fn replace_identifiers(text: &str, identifiers: &[&str], from: &str, to: &str) -> String {
+    if identifiers.is_empty() {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        if identifiers.contains(&word.as_str()) {
+            out.push_str(to);
+            out.push_str(&word[from.len()..]);
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+    for c in text.chars() {
+        if is_ident_char(c) {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_react_component() {
+        let rename = Rename::new("Button", "AdminButton");
+        let source = r#"import styles from './Button.module.css';
+// Button renders a button
+interface ButtonProps { label: string }
+export const Button = ({ label }: ButtonProps) => <button className="button button--primary">{label}</button>;
+Button.displayName = "Button";
+"#;
+        let renamed = rename.source("tsx", source);
+        assert!(renamed.contains("from './AdminButton.module.css'"));
+        assert!(renamed.contains("// Button renders a button"), "comments are kept");
+        assert!(renamed.contains("interface AdminButtonProps"));
+        assert!(renamed.contains("export const AdminButton = ({ label }: AdminButtonProps)"));
+        assert!(renamed.contains(r#"className="admin-button admin-button--primary""#));
+        assert!(renamed.contains("<button "), "HTML tags are kept");
+        assert!(renamed.contains("AdminButton.displayName"));
+        assert_eq!(rename.file_name("Button.module.css"), "AdminButton.module.css");
+        assert_eq!(rename.file_name("ButtonGroup.tsx"), "ButtonGroup.tsx");
+    }
+
+    #[test]
+    fn test_rename_vue_component() {
+        let rename = Rename::new("Button", "AdminButton");
+        let source = r#"<template>
+  <button type="button" :class="['button', active && 'button--active']">Button</button>
+</template>
+<script>
+export default { name: 'Button' }
+</script>
+<style scoped>
+.button { color: red; }
+.button--active, .buttons { color: blue; }
+</style>
+"#;
+        let renamed = rename.source("vue", source);
+        assert!(renamed.contains(r#"type="button""#));
+        assert!(renamed.contains("'admin-button', active && 'admin-button--active'"));
+        assert!(renamed.contains(">Button</button>"));
+        assert!(renamed.contains("name: 'AdminButton'"));
+        assert!(renamed.contains(".admin-button { color: red; }"));
+        assert!(renamed.contains(".admin-button--active, .buttons"));
+    }
+
+    #[test]
+    fn test_kebab_case() {
+        assert_eq!(kebab_case("DataTable"), "data-table");
+        assert_eq!(kebab_case("Button"), "button");
+    }
+}
@@ -0,0 +1,470 @@
+//! npm flavoured version ranges.
+//!
+//! Versions are the `semver` crate's. Its `VersionReq` follows Cargo's rules
+//! though, not npm's (`1.x`, hyphen ranges, `||`, prereleases only matching
+//! when a bound names them), and cannot be intersected, so ranges are parsed
+//! here. A range such as `^1.2.0 || >=2.1.0 <3` is kept as the union of the
+//! intervals it admits, which makes checking a version against it and
+//! comparing ranges with each other straightforward.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use anyhow::{bail, Result};
+use ::semver::{BuildMetadata, Prerelease};
+
+pub use ::semver::Version;
+
+/// Parses a full version such as `1.2.3`, `v1.2.3` or `1.2.3-rc.1+build`.
+/// Build metadata is dropped, as npm ignores it when comparing versions.
+pub fn parse_version(input: &str) -> Result<Version> {
+    match Partial::parse(input)? {
+        Partial { major: Some(major), minor: Some(minor), patch: Some(patch), pre } => {
+            Ok(Version { major, minor, patch, pre, build: BuildMetadata::EMPTY })
+        }
+        _ => bail!("`{}` is not a full version", input),
+    }
+}
+
+/// The lowest prerelease of a version, used as an exclusive upper bound so
+/// that `<2.0.0-0` also rejects `2.0.0-beta`.
+fn floor(major: u64, minor: u64, patch: u64) -> Version {
+    Version { major, minor, patch, pre: Prerelease::new("0").unwrap(), build: BuildMetadata::EMPTY }
+}
+
+fn same_release(a: &Version, b: &Version) -> bool {
+    (a.major, a.minor, a.patch) == (b.major, b.minor, b.patch)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bound {
+    pub version: Version,
+    pub inclusive: bool,
+}
+
+/// The versions between two optional bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interval {
+    pub lower: Option<Bound>,
+    pub upper: Option<Bound>,
+}
+
+impl Interval {
+    const ANY: Interval = Interval { lower: None, upper: None };
+
+    fn contains(&self, version: &Version) -> bool {
+        let above = self.lower.as_ref().is_none_or(|b| match version.cmp(&b.version) {
+            Ordering::Greater => true,
+            Ordering::Equal => b.inclusive,
+            Ordering::Less => false,
+        });
+        let below = self.upper.as_ref().is_none_or(|b| match version.cmp(&b.version) {
+            Ordering::Less => true,
+            Ordering::Equal => b.inclusive,
+            Ordering::Greater => false,
+        });
+        above && below
+    }
+
+    /// Prereleases only match when a bound names a prerelease of the same
+    /// version, so `^1.0.0` does not pick up `1.1.0-beta`.
+    fn allows_prerelease(&self, version: &Version) -> bool {
+        [&self.lower, &self.upper].into_iter()
+            .flatten()
+            .any(|b| !b.version.pre.is_empty() && same_release(&b.version, version))
+    }
+
+    fn is_empty(&self) -> bool {
+        match (&self.lower, &self.upper) {
+            (Some(lower), Some(upper)) => match lower.version.cmp(&upper.version) {
+                Ordering::Greater => true,
+                Ordering::Equal => !(lower.inclusive && upper.inclusive),
+                Ordering::Less => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let mut interval = self.clone();
+        if let Some(lower) = &other.lower {
+            interval.restrict_lower(lower.clone());
+        }
+        if let Some(upper) = &other.upper {
+            interval.restrict_upper(upper.clone());
+        }
+        (!interval.is_empty()).then_some(interval)
+    }
+
+    fn restrict_lower(&mut self, bound: Bound) {
+        let tighter = match &self.lower {
+            None => true,
+            Some(current) => match bound.version.cmp(&current.version) {
+                Ordering::Greater => true,
+                Ordering::Equal => !bound.inclusive,
+                Ordering::Less => false,
+            },
+        };
+        if tighter {
+            self.lower = Some(bound);
+        }
+    }
+
+    fn restrict_upper(&mut self, bound: Bound) {
+        let tighter = match &self.upper {
+            None => true,
+            Some(current) => match bound.version.cmp(&current.version) {
+                Ordering::Less => true,
+                Ordering::Equal => !bound.inclusive,
+                Ordering::Greater => false,
+            },
+        };
+        if tighter {
+            self.upper = Some(bound);
+        }
+    }
+}
+
+/// A version range such as `^3.0.0`, `~1.2`, `>=1 <2`, `1.x || 2.x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    text: String,
+    intervals: Vec<Interval>,
+}
+
+impl Range {
+    pub fn parse(input: &str) -> Result<Self> {
+        let text = input.trim();
+        let mut intervals = Vec::new();
+        for set in text.split("||") {
+            intervals.push(parse_comparator_set(set.trim())
+                .map_err(|e| anyhow::anyhow!("Invalid version range `{}`: {}", text, e))?);
+        }
+        Ok(Self { text: text.to_string(), intervals })
+    }
+
+    pub fn satisfies(&self, version: &Version) -> bool {
+        self.intervals.iter().any(|interval| {
+            interval.contains(version) && (version.pre.is_empty() || interval.allows_prerelease(version))
+        })
+    }
+
+    /// The lowest version the range admits.
+    pub fn min_version(&self) -> Option<Version> {
+        self.intervals.iter()
+            .filter_map(|interval| {
+                let lowest = match &interval.lower {
+                    None => Version::new(0, 0, 0),
+                    Some(b) if b.inclusive => b.version.clone(),
+                    Some(b) if b.version.pre.is_empty() => {
+                        Version::new(b.version.major, b.version.minor, b.version.patch + 1)
+                    }
+                    Some(b) => Version::new(b.version.major, b.version.minor, b.version.patch),
+                };
+                interval.contains(&lowest).then_some(lowest)
+            })
+            .min()
+    }
+
+    /// The versions both ranges admit, or `None` when no version satisfies
+    /// both. When one range already lies within the other it is returned as
+    /// written.
+    pub fn intersect(&self, other: &Range) -> Option<Range> {
+        let intervals: Vec<Interval> = self.intervals.iter()
+            .flat_map(|a| other.intervals.iter().filter_map(move |b| a.intersect(b)))
+            .collect();
+        if intervals.is_empty() {
+            return None;
+        }
+        if intervals == self.intervals {
+            return Some(self.clone());
+        }
+        if intervals == other.intervals {
+            return Some(other.clone());
+        }
+        let text = intervals.iter().map(render).collect::<Vec<_>>().join(" || ");
+        Some(Range { text, intervals })
+    }
+}
+
+/// Writes an interval in the shortest usual notation: `^1.2.0`, `~1.2.0`,
+/// `1.2.0` or `>=1.2.0 <1.5.0`.
+fn render(interval: &Interval) -> String {
+    let (lower, upper) = (&interval.lower, &interval.upper);
+    if let (Some(lower), Some(upper)) = (lower, upper)
+        && lower.inclusive
+    {
+        let v = &lower.version;
+        let caret = match (v.major, v.minor) {
+            (0, 0) => floor(0, 0, v.patch + 1),
+            (0, minor) => floor(0, minor + 1, 0),
+            (major, _) => floor(major + 1, 0, 0),
+        };
+        if !upper.inclusive && upper.version == caret {
+            return format!("^{}", v);
+        }
+        if !upper.inclusive && upper.version == floor(v.major, v.minor + 1, 0) {
+            return format!("~{}", v);
+        }
+        if upper.inclusive && upper.version == *v {
+            return v.to_string();
+        }
+    }
+    let mut parts = Vec::new();
+    if let Some(lower) = lower {
+        parts.push(format!("{}{}", if lower.inclusive { ">=" } else { ">" }, lower.version));
+    }
+    if let Some(upper) = upper {
+        let version = &upper.version;
+        // `<2.0.0-0` reads better as `<2.0.0`, which admits the same releases
+        if !upper.inclusive && version.pre.as_str() == "0" {
+            parts.push(format!("<{}.{}.{}", version.major, version.minor, version.patch));
+        } else {
+            parts.push(format!("{}{}", if upper.inclusive { "<=" } else { "<" }, version));
+        }
+    }
+    if parts.is_empty() {
+        "*".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", if self.text.is_empty() { "*" } else { &self.text })
+    }
+}
+
+/// A version with possibly missing or wildcard (`x`, `*`) parts.
+#[derive(Debug)]
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Prerelease,
+}
+
+impl Partial {
+    fn parse(input: &str) -> Result<Self> {
+        let input = input.trim().trim_start_matches(['v', '=']);
+        let input = input.split_once('+').map_or(input, |(version, _build)| version);
+        let (release, pre) = match input.split_once('-') {
+            Some((release, pre)) => {
+                (release, Prerelease::new(pre).map_err(|e| anyhow::anyhow!("`{}` has an invalid prerelease: {}", input, e))?)
+            }
+            None => (input, Prerelease::EMPTY),
+        };
+        let mut parts = [None; 3];
+        let mut wildcard = false;
+        for (i, part) in release.split('.').enumerate() {
+            if i >= 3 {
+                bail!("`{}` has too many parts", input);
+            }
+            if matches!(part, "x" | "X" | "*") || (part.is_empty() && i == 0) {
+                wildcard = true;
+                continue;
+            }
+            if wildcard {
+                bail!("`{}` has a number after a wildcard", input);
+            }
+            parts[i] = Some(part.parse().map_err(|_| anyhow::anyhow!("`{}` is not a version", input))?);
+        }
+        let [major, minor, patch] = parts;
+        Ok(Self { major, minor, patch, pre })
+    }
+
+    fn is_full(&self) -> bool {
+        self.patch.is_some()
+    }
+
+    /// The lowest version this partial matches.
+    fn low(&self) -> Version {
+        Version {
+            major: self.major.unwrap_or(0),
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+            build: BuildMetadata::EMPTY,
+        }
+    }
+
+    /// The exclusive upper bound of the versions this partial matches, e.g.
+    /// `1.3.0-0` for `1.2`. `None` for `*`.
+    fn next(&self) -> Option<Version> {
+        match (self.major, self.minor, self.patch) {
+            (None, _, _) => None,
+            (Some(major), None, _) => Some(floor(major + 1, 0, 0)),
+            (Some(major), Some(minor), None) => Some(floor(major, minor + 1, 0)),
+            (Some(major), Some(minor), Some(patch)) => Some(floor(major, minor, patch + 1)),
+        }
+    }
+}
+
+fn parse_comparator_set(set: &str) -> Result<Interval> {
+    if let Some((from, to)) = set.split_once(" - ") {
+        let from = Partial::parse(from)?;
+        let to = Partial::parse(to)?;
+        let mut interval = Interval::ANY;
+        interval.restrict_lower(Bound { version: from.low(), inclusive: true });
+        if to.is_full() {
+            interval.restrict_upper(Bound { version: to.low(), inclusive: true });
+        } else if let Some(next) = to.next() {
+            interval.restrict_upper(Bound { version: next, inclusive: false });
+        }
+        return Ok(interval);
+    }
+
+    let mut interval = Interval::ANY;
+    for comparator in comparators(set) {
+        let (op, version) = split_operator(&comparator);
+        apply(&mut interval, op, Partial::parse(version)?)?;
+    }
+    Ok(interval)
+}
+
+/// Splits a comparator set on whitespace, keeping operators attached to the
+/// version that follows them (`>= 1.2` is one comparator).
+fn comparators(set: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for word in set.split_whitespace() {
+        match out.last_mut() {
+            Some(last) if last.chars().all(|c| "<>=~^".contains(c)) => last.push_str(word),
+            _ => out.push(word.to_string()),
+        }
+    }
+    out
+}
+
+fn split_operator(comparator: &str) -> (&str, &str) {
+    let end = comparator.find(|c: char| !"<>=~^".contains(c)).unwrap_or(comparator.len());
+    (&comparator[..end], &comparator[end..])
+}
+
+fn apply(interval: &mut Interval, op: &str, partial: Partial) -> Result<()> {
+    let low = partial.low();
+    let at_least = |version: Version| Bound { version, inclusive: true };
+    let below = |version: Version| Bound { version, inclusive: false };
+    match op {
+        "" | "=" => {
+            interval.restrict_lower(at_least(low));
+            match partial.next() {
+                Some(_) if partial.is_full() => interval.restrict_upper(Bound { version: partial.low(), inclusive: true }),
+                Some(next) => interval.restrict_upper(below(next)),
+                None => {}
+            }
+        }
+        "^" => {
+            interval.restrict_lower(at_least(low));
+            let upper = match (partial.major, partial.minor, partial.patch) {
+                (None, _, _) => None,
+                (Some(0), Some(0), Some(patch)) => Some(floor(0, 0, patch + 1)),
+                (Some(0), Some(minor), _) => Some(floor(0, minor + 1, 0)),
+                (Some(major), _, _) => Some(floor(major + 1, 0, 0)),
+            };
+            if let Some(upper) = upper {
+                interval.restrict_upper(below(upper));
+            }
+        }
+        "~" | "~>" => {
+            interval.restrict_lower(at_least(low));
+            let upper = match (partial.major, partial.minor) {
+                (None, _) => None,
+                (Some(major), None) => Some(floor(major + 1, 0, 0)),
+                (Some(major), Some(minor)) => Some(floor(major, minor + 1, 0)),
+            };
+            if let Some(upper) = upper {
+                interval.restrict_upper(below(upper));
+            }
+        }
+        ">=" => interval.restrict_lower(at_least(low)),
+        ">" => match partial.next() {
+            Some(_) if partial.is_full() => interval.restrict_lower(Bound { version: low, inclusive: false }),
+            // `>1.2` means `>=1.3.0`
+            Some(next) => interval.restrict_lower(at_least(Version::new(next.major, next.minor, next.patch))),
+            // `>*` matches nothing
+            None => interval.restrict_upper(below(floor(0, 0, 0))),
+        },
+        "<" => {
+            if partial.major.is_some() {
+                interval.restrict_upper(below(if partial.is_full() || !low.pre.is_empty() {
+                    low
+                } else {
+                    floor(low.major, low.minor, low.patch)
+                }));
+            } else {
+                interval.restrict_upper(below(floor(0, 0, 0)));
+            }
+        }
+        "<=" => match partial.next() {
+            Some(_) if partial.is_full() => interval.restrict_upper(at_least(low)),
+            Some(next) => interval.restrict_upper(below(next)),
+            None => {}
+        },
+        other => bail!("unknown operator `{}`", other),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(range: &str, version: &str) -> bool {
+        Range::parse(range).unwrap().satisfies(&parse_version(version).unwrap())
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let mut versions: Vec<Version> = ["1.0.0", "1.0.0-rc.1", "1.0.0-beta.11", "1.0.0-beta.2", "0.9.9", "1.0.0-alpha"]
+            .iter()
+            .map(|v| parse_version(v).unwrap())
+            .collect();
+        versions.sort();
+        let sorted: Vec<String> = versions.iter().map(ToString::to_string).collect();
+        assert_eq!(sorted, ["0.9.9", "1.0.0-alpha", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0"]);
+    }
+
+    #[test]
+    fn test_range_satisfies() {
+        assert!(satisfies("^18.0.0", "18.2.0"));
+        assert!(!satisfies("^18.0.0", "19.0.0"));
+        assert!(!satisfies("^0.2.3", "0.3.0"));
+        assert!(satisfies("~1.2", "1.2.9"));
+        assert!(!satisfies("~1.2.3", "1.3.0"));
+        assert!(satisfies("1.x || >=2.5.0 <3", "2.6.1"));
+        assert!(!satisfies("1.x || >=2.5.0 <3", "2.4.0"));
+        assert!(satisfies("1.2.3 - 2.3", "2.3.9"));
+        assert!(satisfies(">= 1.2", "1.2.0"));
+        assert!(!satisfies(">1.2", "1.2.5"));
+        assert!(satisfies("*", "4.0.0"));
+        assert!(satisfies("3", "3.4.21"));
+        assert!(!satisfies("^1.0.0", "1.1.0-beta"), "prereleases need an explicit opt-in");
+        assert!(satisfies("^1.1.0-beta", "1.1.0-beta.2"));
+        assert!(Range::parse("latest").is_err());
+    }
+
+    #[test]
+    fn test_intersect() {
+        let intersect = |a: &str, b: &str| {
+            Range::parse(a).unwrap().intersect(&Range::parse(b).unwrap()).map(|r| r.to_string())
+        };
+        assert_eq!(intersect("^18.0.0", "^18.2.0").as_deref(), Some("^18.2.0"));
+        assert_eq!(intersect("^18.0.0", "^17.0.0"), None);
+        assert_eq!(intersect("~1.2.0", "^1.0.0").as_deref(), Some("~1.2.0"));
+        assert_eq!(intersect(">=1.5.0", "^1.0.0").as_deref(), Some("^1.5.0"));
+        assert_eq!(intersect(">=1.2.0 <1.8.0", ">=1.4.0 <2").as_deref(), Some(">=1.4.0 <1.8.0"));
+        assert_eq!(intersect("^2.0.0 || ^3.0.0", "^3.1.0").as_deref(), Some("^3.1.0"));
+        assert_eq!(intersect("*", "3.4.21").as_deref(), Some("3.4.21"));
+        assert_eq!(intersect("<1.0.0", ">=1.0.0"), None);
+    }
+
+    #[test]
+    fn test_min_version() {
+        let min = |range: &str| Range::parse(range).unwrap().min_version().map(|v| v.to_string());
+        assert_eq!(min("^3.4.0").as_deref(), Some("3.4.0"));
+        assert_eq!(min(">1.2.3").as_deref(), Some("1.2.4"));
+        assert_eq!(min("2.x || ^1.5").as_deref(), Some("1.5.0"));
+        assert_eq!(min("*").as_deref(), Some("0.0.0"));
+    }
+}
@@ -0,0 +1,179 @@
+//! Lightweight scanning of component source files.
+//!
+//! Just enough lexing to tell code from string literals and comments in
+//! JavaScript and TypeScript, and to find the script blocks of single-file
+//! components. This is not a parser; it errs on the side of leaving text alone.
+
+use std::ops::Range;
+
+pub enum Token<'a> {
+    Code(&'a str),
+    Str(&'a str),
+    Comment(&'a str),
+}
+
+/// Splits JavaScript/TypeScript into code, string literals and comments.
+pub fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let bytes = code.as_bytes();
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let (end, is_string) = match c {
+            b'"' | b'\'' | b'`' => match string_end(bytes, i) {
+                Some(end) => (end, true),
+                // An unterminated quote is treated as code
+                None => {
+                    i += 1;
+                    continue;
+                }
+            },
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                (code[i..].find('\n').map(|n| i + n).unwrap_or(bytes.len()), false)
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                (code[i + 2..].find("*/").map(|n| i + 2 + n + 2).unwrap_or(bytes.len()), false)
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        if start < i {
+            tokens.push(Token::Code(&code[start..i]));
+        }
+        let text = &code[i..end];
+        tokens.push(if is_string { Token::Str(text) } else { Token::Comment(text) });
+        start = end;
+        i = end;
+    }
+    if start < bytes.len() {
+        tokens.push(Token::Code(&code[start..]));
+    }
+    tokens
+}
+
+/// The end (exclusive) of the string literal starting at `start`, if it is
+/// terminated. Only template literals may span lines.
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let quote = bytes[start];
+    let mut j = start + 1;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' => j += 2,
+            b'\n' if quote != b'`' => return None,
+            c if c == quote => return Some(j + 1),
+            _ => j += 1,
+        }
+    }
+    None
+}
+
+pub fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Byte ranges of the script code in a file with the given extension: the
+/// `<script>` blocks of `.vue`, `.svelte` and `.html` files, or the whole file
+/// for JavaScript and TypeScript. Other files have no script code.
+pub fn script_ranges(ext: &str, content: &str) -> Vec<Range<usize>> {
+    match ext {
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => std::iter::once(0..content.len()).collect(),
+        "vue" | "svelte" | "html" => {
+            let mut ranges = Vec::new();
+            let mut offset = 0;
+            while let Some(i) = content[offset..].find("<script") {
+                let Some(body_start) = content[offset + i..].find('>').map(|j| offset + i + j + 1) else {
+                    break;
+                };
+                let body_end = content[body_start..].find("</script>")
+                    .map(|j| body_start + j)
+                    .unwrap_or(content.len());
+                ranges.push(body_start..body_end);
+                offset = body_end;
+            }
+            ranges
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Replaces the contents of string literals in the script code of a file,
+/// keeping their quotes. `f` returns the new contents, or `None` to keep them.
+pub fn map_string_literals(ext: &str, content: &str, mut f: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+    for range in script_ranges(ext, content) {
+        out.push_str(&content[last..range.start]);
+        for token in tokenize(&content[range.clone()]) {
+            match token {
+                Token::Str(literal) => {
+                    let quote = &literal[..1];
+                    match f(&literal[1..literal.len() - 1]) {
+                        Some(replacement) => out.push_str(&format!("{quote}{replacement}{quote}")),
+                        None => out.push_str(literal),
+                    }
+                }
+                Token::Code(text) | Token::Comment(text) => out.push_str(text),
+            }
+        }
+        last = range.end;
+    }
+    out.push_str(&content[last..]);
+    out
+}
+
+/// The module specifiers imported by the script code of a file: static
+/// `import`/`export ... from`, side-effect imports, dynamic `import()` and
+/// `require()`.
+pub fn import_specifiers(ext: &str, content: &str) -> Vec<String> {
+    let mut specifiers = Vec::new();
+    for range in script_ranges(ext, content) {
+        let mut last_code = "";
+        for token in tokenize(&content[range]) {
+            match token {
+                Token::Code(text) => last_code = text,
+                Token::Str(literal) => {
+                    let before = last_code.trim_end();
+                    let is_import = ["from", "import", "require(", "import("].iter().any(|keyword| {
+                        before.strip_suffix(keyword)
+                            .is_some_and(|rest| !rest.ends_with(is_ident_char))
+                    });
+                    if is_import {
+                        specifiers.push(literal[1..literal.len() - 1].to_string());
+                    }
+                    last_code = "";
+                }
+                Token::Comment(_) => {}
+            }
+        }
+    }
+    specifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_specifiers() {
+        let vue = r#"<template><p>import "nothing" from here</p></template>
+<script setup lang="ts">
+import { ref } from 'vue'
+import Icon from "../Icon/Icon.vue";
+import './styles.css';
+// import 'commented-out';
+const fmt = await import('date-fns');
+const { debounce } = require("lodash-es");
+const label = "from";
+export { helper } from '@/utils/helper';
+</script>
+"#;
+        assert_eq!(
+            import_specifiers("vue", vue),
+            vec!["vue", "../Icon/Icon.vue", "./styles.css", "date-fns", "lodash-es", "@/utils/helper"]
+        );
+        assert!(import_specifiers("css", "@import 'x';").is_empty());
+    }
+}
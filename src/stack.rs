@@ -0,0 +1,197 @@
+//! Detection of the frameworks, styles and languages a project uses, from
+//! its manifests and config files. `init` pre-selects what is found here.
+
+use std::fs;
+use std::path::Path;
+
+use crate::taxonomy::{Entry, Taxonomy};
+
+/// A detected value and why it was picked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detected {
+    pub value: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StackDetection {
+    pub framework: Vec<Detected>,
+    pub style: Vec<Detected>,
+    pub language: Vec<Detected>,
+}
+
+/// npm packages marking a framework, in order of preference: meta-frameworks
+/// come before the library they build on.
+const NPM_FRAMEWORKS: &[(&str, &[&str])] = &[
+    ("nuxt", &["nuxt", "nuxt3"]),
+    ("vue", &["vue"]),
+    ("nextjs", &["next"]),
+    ("remix", &["@remix-run/react"]),
+    ("react", &["react"]),
+    ("svelte", &["svelte", "@sveltejs/kit"]),
+    ("angular", &["@angular/core"]),
+    ("solidjs", &["solid-js"]),
+    ("ember", &["ember-source"]),
+    ("express", &["express"]),
+    ("jquery", &["jquery"]),
+];
+
+const NPM_STYLES: &[(&str, &[&str])] = &[
+    ("tailwind", &["tailwindcss"]),
+    ("bootstrap", &["bootstrap", "bootstrap-vue", "react-bootstrap"]),
+    ("scss", &["sass", "sass-embedded", "node-sass"]),
+];
+
+const PYTHON_FRAMEWORKS: &[&str] = &["django", "flask", "fastapi"];
+
+const TAILWIND_CONFIGS: &[&str] = &["tailwind.config.js", "tailwind.config.cjs", "tailwind.config.mjs", "tailwind.config.ts"];
+
+impl StackDetection {
+    pub fn is_empty(&self) -> bool {
+        self.framework.is_empty() && self.style.is_empty() && self.language.is_empty()
+    }
+
+    fn add(list: &mut Vec<Detected>, value: &str, reason: String) {
+        if !list.iter().any(|d| d.value == value) {
+            list.push(Detected { value: value.to_string(), reason });
+        }
+    }
+
+    pub fn values(list: &[Detected]) -> Vec<String> {
+        list.iter().map(|d| d.value.clone()).collect()
+    }
+}
+
+/// Inspects the manifests and config files in `dir`. Frameworks and styles
+/// of `taxonomy` are also detected from their npm dependencies.
+pub fn detect_stack(dir: &Path, taxonomy: &Taxonomy) -> StackDetection {
+    let mut detection = StackDetection::default();
+
+    if let Some(package) = fs::read_to_string(dir.join("package.json")).ok().and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) {
+        let mut dependencies: Vec<(&str, &str)> = Vec::new();
+        for section in ["dependencies", "devDependencies", "peerDependencies"] {
+            for (name, range) in package.get(section).and_then(serde_json::Value::as_object).into_iter().flatten() {
+                dependencies.push((name, range.as_str().unwrap_or("*")));
+            }
+        }
+        let find = |packages: &[&str]| dependencies.iter().find(|(name, _)| packages.contains(name)).copied();
+
+        for (framework, packages) in NPM_FRAMEWORKS {
+            if let Some((name, range)) = find(packages) {
+                StackDetection::add(&mut detection.framework, framework, format!("package.json depends on {} {}", name, range));
+            }
+        }
+        for (style, packages) in NPM_STYLES {
+            if let Some((name, range)) = find(packages) {
+                StackDetection::add(&mut detection.style, style, format!("package.json depends on {} {}", name, range));
+            }
+        }
+        let by_dependencies = |list: &mut Vec<Detected>, entries: &[Entry]| {
+            for entry in entries {
+                let packages: Vec<&str> = entry.dependencies.iter().map(|(p, _)| p.as_str()).collect();
+                if let Some((name, range)) = find(&packages) {
+                    StackDetection::add(list, &entry.id, format!("package.json depends on {} {}", name, range));
+                }
+            }
+        };
+        by_dependencies(&mut detection.framework, &taxonomy.frameworks);
+        by_dependencies(&mut detection.style, &taxonomy.styles);
+        match find(&["typescript"]) {
+            Some((_, range)) => StackDetection::add(&mut detection.language, "typescript", format!("package.json depends on typescript {}", range)),
+            None if !dir.join("tsconfig.json").exists() => {
+                StackDetection::add(&mut detection.language, "javascript", "package.json without TypeScript".to_string())
+            }
+            None => {}
+        }
+    }
+
+    if let Some(config) = TAILWIND_CONFIGS.iter().find(|file| dir.join(file).exists()) {
+        StackDetection::add(&mut detection.style, "tailwind", format!("{} found", config));
+    }
+    if dir.join("tsconfig.json").exists() {
+        StackDetection::add(&mut detection.language, "typescript", "tsconfig.json found".to_string());
+    }
+
+    if let Some(composer) = fs::read_to_string(dir.join("composer.json")).ok().and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) {
+        let requires_laravel = composer.get("require")
+            .and_then(serde_json::Value::as_object)
+            .is_some_and(|require| require.contains_key("laravel/framework"));
+        if requires_laravel {
+            StackDetection::add(&mut detection.framework, "laravel", "composer.json requires laravel/framework".to_string());
+        } else {
+            StackDetection::add(&mut detection.framework, "php", "composer.json found".to_string());
+        }
+        StackDetection::add(&mut detection.language, "php", "composer.json found".to_string());
+    }
+
+    for manifest in ["requirements.txt", "pyproject.toml"] {
+        let Ok(content) = fs::read_to_string(dir.join(manifest)) else {
+            continue;
+        };
+        let content = content.to_lowercase();
+        for framework in PYTHON_FRAMEWORKS {
+            let listed = content.lines().any(|line| {
+                let line = line.trim().trim_start_matches(['"', '\'']);
+                line.strip_prefix(framework).is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_'))
+            });
+            if listed {
+                StackDetection::add(&mut detection.framework, framework, format!("{} lists {}", manifest, framework));
+            }
+        }
+        StackDetection::add(&mut detection.language, "python", format!("{} found", manifest));
+    }
+
+    if let Ok(gemfile) = fs::read_to_string(dir.join("Gemfile")) {
+        if gemfile.lines().any(|line| line.trim().starts_with("gem 'rails'") || line.trim().starts_with("gem \"rails\"")) {
+            StackDetection::add(&mut detection.framework, "rails", "Gemfile includes rails".to_string());
+        }
+        StackDetection::add(&mut detection.language, "ruby", "Gemfile found".to_string());
+    }
+
+    detection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_stack() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(
+            dir.join("package.json"),
+            r#"{ "dependencies": { "nuxt": "^3.10.0", "vue": "^3.4.0" }, "devDependencies": { "sass": "^1.70.0" } }"#,
+        )
+        .unwrap();
+        fs::write(dir.join("tailwind.config.ts"), "export default {}").unwrap();
+        fs::write(dir.join("tsconfig.json"), "{}").unwrap();
+        fs::write(dir.join("requirements.txt"), "Django>=4.2\nflask-cors==4.0\n").unwrap();
+        fs::write(dir.join("Gemfile"), "source 'https://rubygems.org'\ngem 'rails', '~> 7.1'\n").unwrap();
+
+        let detection = detect_stack(dir, &Taxonomy::builtin());
+        let summary = |list: &[Detected]| list.iter().map(|d| format!("{}: {}", d.value, d.reason)).collect::<Vec<_>>();
+        assert_eq!(
+            summary(&detection.framework),
+            [
+                "nuxt: package.json depends on nuxt ^3.10.0",
+                "vue: package.json depends on vue ^3.4.0",
+                "django: requirements.txt lists django",
+                "rails: Gemfile includes rails",
+            ]
+        );
+        assert_eq!(summary(&detection.style), ["scss: package.json depends on sass ^1.70.0", "tailwind: tailwind.config.ts found"]);
+        assert_eq!(
+            summary(&detection.language),
+            ["typescript: tsconfig.json found", "python: requirements.txt found", "ruby: Gemfile found"]
+        );
+
+        assert!(detect_stack(&dir.join("missing"), &Taxonomy::builtin()).is_empty());
+
+        let mut taxonomy = Taxonomy::builtin();
+        taxonomy.merge(&toml::from_str("[styles.unocss]\ndependencies = { unocss = \"^0.58.0\" }").unwrap());
+        fs::write(dir.join("package.json"), r#"{ "devDependencies": { "unocss": "^0.58.5" } }"#).unwrap();
+        let detection = detect_stack(dir, &taxonomy);
+        assert_eq!(summary(&detection.style), ["unocss: package.json depends on unocss ^0.58.5", "tailwind: tailwind.config.ts found"]);
+    }
+}
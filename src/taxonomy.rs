@@ -0,0 +1,242 @@
+//! The frameworks, styles and languages components are filed under.
+//!
+//! The built-in entries can be extended and changed from the `[taxonomy]`
+//! tables of the global config and then the project config. The merged lists
+//! are what `init` offers, what `export` infers from and the order `show`
+//! lists the library in.
+
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::config::{GlobalConfig, TaxonomyConfig, TaxonomyEntry};
+use crate::utils::SUPPORTED_EXTENSIONS;
+
+/// A framework, style or language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Name used in configs and library paths, e.g. `nextjs`
+    pub id: String,
+    /// Name shown to users, e.g. `Next.js`
+    pub name: String,
+    pub extensions: Vec<String>,
+    /// npm packages and ranges every component using it requires
+    pub dependencies: Vec<(String, String)>,
+    pub markers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Taxonomy {
+    pub frameworks: Vec<Entry>,
+    pub styles: Vec<Entry>,
+    pub languages: Vec<Entry>,
+}
+
+struct Builtin {
+    id: &'static str,
+    name: &'static str,
+    extensions: &'static [&'static str],
+    dependencies: &'static [(&'static str, &'static str)],
+    markers: &'static [&'static str],
+}
+
+const fn builtin(id: &'static str, name: &'static str, extensions: &'static [&'static str]) -> Builtin {
+    Builtin { id, name, extensions, dependencies: &[], markers: &[] }
+}
+
+const FRAMEWORKS: &[Builtin] = &[
+    builtin("none", "None", &[]),
+    Builtin { dependencies: &[("vue", "^3.0.0")], ..builtin("vue", "Vue", &["vue"]) },
+    builtin("nuxt", "Nuxt", &["vue"]),
+    builtin("angular", "Angular", &[]),
+    Builtin {
+        dependencies: &[("react", "^18.0.0"), ("react-dom", "^18.0.0")],
+        ..builtin("react", "React", &["tsx", "jsx"])
+    },
+    builtin("svelte", "Svelte", &["svelte"]),
+    builtin("php", "PHP", &["php"]),
+    builtin("vanilla", "Vanilla", &[]),
+    builtin("ember", "Ember", &[]),
+    builtin("jquery", "jQuery", &[]),
+    builtin("laravel", "Laravel", &["php"]),
+    builtin("django", "Django", &["py"]),
+    builtin("flask", "Flask", &["py"]),
+    builtin("rails", "Rails", &["rb"]),
+    builtin("spring", "Spring", &[]),
+    builtin("express", "Express", &[]),
+    builtin("fastapi", "FastAPI", &["py"]),
+    builtin("nextjs", "Next.js", &["tsx", "jsx"]),
+    builtin("remix", "Remix", &["tsx", "jsx"]),
+    builtin("solidjs", "SolidJS", &["tsx", "jsx"]),
+    builtin(".net", ".NET", &[]),
+];
+
+const STYLES: &[Builtin] = &[
+    builtin("none", "None", &[]),
+    Builtin {
+        dependencies: &[("tailwindcss", "^3.0.0")],
+        markers: &["px-", "py-", "bg-", "text-", "rounded", "flex "],
+        ..builtin("tailwind", "Tailwind CSS", &[])
+    },
+    Builtin {
+        markers: &["btn btn-", "\"btn ", "container-fluid", "col-md-", "bootstrap"],
+        ..builtin("bootstrap", "Bootstrap", &[])
+    },
+    Builtin { markers: &["lang=\"scss\"", "lang='scss'", ".scss"], ..builtin("scss", "SCSS", &["scss"]) },
+];
+
+const LANGUAGES: &[Builtin] = &[
+    builtin("none", "None", &[]),
+    builtin("javascript", "JavaScript", &["js", "jsx", "mjs", "cjs", "vue", "svelte"]),
+    Builtin { markers: &["lang=\"ts\"", "lang='ts'"], ..builtin("typescript", "TypeScript", &["ts", "tsx"]) },
+    builtin("python", "Python", &["py"]),
+    builtin("ruby", "Ruby", &["rb"]),
+    builtin("php", "PHP", &["php"]),
+    builtin("java", "Java", &["java"]),
+    builtin("csharp", "C#", &["cs"]),
+    builtin("go", "Go", &["go"]),
+    builtin("rust", "Rust", &["rs"]),
+    builtin("swift", "Swift", &["swift"]),
+    builtin("kotlin", "Kotlin", &["kt"]),
+    builtin("dart", "Dart", &["dart"]),
+    builtin("elixir", "Elixir", &["ex", "exs"]),
+    builtin("scala", "Scala", &["scala"]),
+    builtin("lua", "Lua", &["lua"]),
+    builtin("perl", "Perl", &["pl"]),
+];
+
+impl Entry {
+    fn from_builtin(builtin: &Builtin) -> Self {
+        Self {
+            id: builtin.id.to_string(),
+            name: builtin.name.to_string(),
+            extensions: builtin.extensions.iter().map(|e| e.to_string()).collect(),
+            dependencies: builtin.dependencies.iter().map(|(p, r)| (p.to_string(), r.to_string())).collect(),
+            markers: builtin.markers.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    fn apply(&mut self, config: &TaxonomyEntry) {
+        if let Some(name) = &config.name {
+            self.name = name.clone();
+        }
+        if !config.extensions.is_empty() {
+            self.extensions = config.extensions.clone();
+        }
+        if !config.dependencies.is_empty() {
+            self.dependencies = config.dependencies.iter().map(|(p, r)| (p.clone(), r.clone())).collect();
+        }
+        if !config.markers.is_empty() {
+            self.markers = config.markers.clone();
+        }
+    }
+}
+
+/// Shows the name, which is what prompts list and scripted answers match.
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Taxonomy {
+    pub fn builtin() -> Self {
+        let entries = |list: &[Builtin]| list.iter().map(Entry::from_builtin).collect();
+        Self { frameworks: entries(FRAMEWORKS), styles: entries(STYLES), languages: entries(LANGUAGES) }
+    }
+
+    /// The built-in entries merged with the global config and then `project`.
+    pub fn load(project: &TaxonomyConfig) -> Result<Self> {
+        let mut taxonomy = Self::builtin();
+        taxonomy.merge(&GlobalConfig::load()?.taxonomy);
+        taxonomy.merge(project);
+        Ok(taxonomy)
+    }
+
+    /// Changes the entries `config` names and appends the new ones, in id order.
+    pub fn merge(&mut self, config: &TaxonomyConfig) {
+        merge_entries(&mut self.frameworks, &config.frameworks);
+        merge_entries(&mut self.styles, &config.styles);
+        merge_entries(&mut self.languages, &config.languages);
+    }
+
+    /// Whether files with this extension can be components.
+    pub fn supports_extension(&self, ext: &str) -> bool {
+        SUPPORTED_EXTENSIONS.contains(&ext)
+            || [&self.frameworks, &self.styles, &self.languages]
+                .iter()
+                .any(|entries| entries.iter().any(|e| e.extensions.iter().any(|x| x == ext)))
+    }
+}
+
+fn merge_entries(entries: &mut Vec<Entry>, config: &std::collections::BTreeMap<String, TaxonomyEntry>) {
+    for (id, entry) in config {
+        match entries.iter_mut().find(|e| e.id == *id) {
+            Some(existing) => existing.apply(entry),
+            None => {
+                let mut added = Entry {
+                    id: id.clone(),
+                    name: id.clone(),
+                    extensions: Vec::new(),
+                    dependencies: Vec::new(),
+                    markers: Vec::new(),
+                };
+                added.apply(entry);
+                entries.push(added);
+            }
+        }
+    }
+}
+
+/// The entry with id `id`, if any.
+pub fn find<'a>(entries: &'a [Entry], id: &str) -> Option<&'a Entry> {
+    entries.iter().find(|e| e.id == id)
+}
+
+/// Where `id` sorts among `entries`; unknown ids sort last.
+pub fn position(entries: &[Entry], id: &str) -> usize {
+    entries.iter().position(|e| e.id == id).unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_taxonomy() {
+        let config: TaxonomyConfig = toml::from_str(
+            r#"
+[styles.unocss]
+name = "UnoCSS"
+dependencies = { unocss = "^0.58.0" }
+markers = ["i-carbon-"]
+
+[styles.css-modules]
+name = "CSS modules"
+extensions = ["css"]
+
+[styles.tailwind]
+dependencies = { tailwindcss = "^4.0.0" }
+
+[frameworks.vuetify]
+name = "Vuetify"
+extensions = ["vue"]
+"#,
+        )
+        .unwrap();
+        let mut taxonomy = Taxonomy::builtin();
+        taxonomy.merge(&config);
+
+        let ids: Vec<&str> = taxonomy.styles.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["none", "tailwind", "bootstrap", "scss", "css-modules", "unocss"]);
+        let tailwind = find(&taxonomy.styles, "tailwind").unwrap();
+        assert_eq!(tailwind.to_string(), "Tailwind CSS");
+        assert_eq!(tailwind.dependencies, [("tailwindcss".to_string(), "^4.0.0".to_string())]);
+        assert!(!tailwind.markers.is_empty());
+        assert_eq!(find(&taxonomy.styles, "unocss").unwrap().name, "UnoCSS");
+        assert_eq!(position(&taxonomy.frameworks, "vuetify"), FRAMEWORKS.len());
+        assert_eq!(position(&taxonomy.frameworks, "qwik"), usize::MAX);
+        assert!(taxonomy.supports_extension("css"));
+        assert!(!Taxonomy::builtin().supports_extension("hbs"));
+    }
+}
@@ -6,20 +6,156 @@ pub const SUPPORTED_EXTENSIONS: &[&str] = &[
 ];
 
 
-pub const SUPPORTED_FRAMEWORKS: &[&str] = &[
-	"none", "vue", "nuxt", "angular", "react", "svelte", "php", 
-	"vanilla", "ember", "jquery", "laravel", "django", "flask",
-	"rails", "spring", "express", "fastapi", "nextjs", "remix",
-	"solidjs", ".net"
-];
+/// Returns the files matching a glob pattern such as `src/components/**/*.vue`
+/// or an absolute one. Results are sorted for a stable order.
+pub fn expand_glob(pattern: &str) -> anyhow::Result<Vec<std::path::PathBuf>> {
+	let paths = glob::glob(pattern)
+		.map_err(|e| anyhow::anyhow!("Invalid glob `{}`: {}", pattern, e))?;
+	let mut matches: Vec<_> = paths
+		.filter_map(|entry| entry.ok())
+		.filter(|path| path.is_file())
+		.collect();
+	matches.sort();
+	Ok(matches)
+}
 
-pub const SUPPORTED_STYLES: &[&str] = &[
-	"none", "tailwind", "bootstrap", "scss",
-];
+/// Matches a single path segment against a pattern with `*` and `?` wildcards.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+	let pattern: Vec<char> = pattern.chars().collect();
+	let text: Vec<char> = text.chars().collect();
+	let (mut p, mut t) = (0, 0);
+	let mut backtrack: Option<(usize, usize)> = None;
+	while t < text.len() {
+		if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+			p += 1;
+			t += 1;
+		} else if p < pattern.len() && pattern[p] == '*' {
+			backtrack = Some((p, t));
+			p += 1;
+		} else if let Some((star_p, star_t)) = backtrack {
+			p = star_p + 1;
+			t = star_t + 1;
+			backtrack = Some((star_p, star_t + 1));
+		} else {
+			return false;
+		}
+	}
+	pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Resolves `.` and `..` components without touching the file system.
+pub fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
+	use std::path::Component;
+	let mut out = std::path::PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			// Leading `..` components are kept, they cannot be resolved
+			Component::ParentDir => match out.components().next_back() {
+				None | Some(Component::ParentDir) => out.push(".."),
+				Some(Component::RootDir | Component::Prefix(_)) => {}
+				Some(_) => {
+					out.pop();
+				}
+			},
+			other => out.push(other),
+		}
+	}
+	out
+}
+
+/// The relative path from directory `from` to `to`, as used in an import
+/// specifier (`./Pagination.vue`, `../table/Pagination.vue`).
+pub fn relative_import_path(from: &std::path::Path, to: &std::path::Path) -> String {
+	let (from, to) = (normalize_path(from), normalize_path(to));
+	let from: Vec<_> = from.components().collect();
+	let to: Vec<_> = to.components().collect();
+	let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+	let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+	parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+	let joined = parts.join("/");
+	if joined.starts_with("..") {
+		joined
+	} else {
+		format!("./{}", joined)
+	}
+}
+
+/// Finds `file_name` in the directory of `path` or the closest ancestor
+/// containing it.
+pub fn find_upwards(path: &std::path::Path, file_name: &str) -> Option<std::path::PathBuf> {
+	let start = if path.is_dir() { path } else { path.parent()? };
+	let start = std::path::absolute(start).ok()?;
+	start.ancestors()
+		.map(|dir| dir.join(file_name))
+		.find(|candidate| candidate.is_file())
+}
+
+/// Quotes a shell word when it contains characters the shell would
+/// interpret, e.g. `'vue@>=3.2.0 <4.0.0'`.
+pub fn shell_quote(word: &str) -> String {
+	let safe = !word.is_empty()
+		&& word.chars().all(|c| c.is_ascii_alphanumeric() || "@/._-^~=:+,%".contains(c));
+	if safe {
+		word.to_string()
+	} else {
+		format!("'{}'", word.replace('\'', "'\\''"))
+	}
+}
+
+/// A line-by-line diff of two texts as (`' '`, `'-'` or `'+'`, line), using the
+/// longest common subsequence of lines.
+pub fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<(char, &'a str)> {
+	let old: Vec<&str> = old.lines().collect();
+	let new: Vec<&str> = new.lines().collect();
+	// common[i][j]: length of the LCS of old[i..] and new[j..]
+	let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+	for i in (0..old.len()).rev() {
+		for j in (0..new.len()).rev() {
+			common[i][j] = if old[i] == new[j] {
+				common[i + 1][j + 1] + 1
+			} else {
+				common[i + 1][j].max(common[i][j + 1])
+			};
+		}
+	}
+
+	let mut diff = Vec::new();
+	let (mut i, mut j) = (0, 0);
+	while i < old.len() || j < new.len() {
+		if i < old.len() && j < new.len() && old[i] == new[j] {
+			diff.push((' ', old[i]));
+			i += 1;
+			j += 1;
+		} else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+			diff.push(('-', old[i]));
+			i += 1;
+		} else {
+			diff.push(('+', new[j]));
+			j += 1;
+		}
+	}
+	diff
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::Path;
+
+	#[test]
+	fn test_normalize_path() {
+		assert_eq!(normalize_path(Path::new("a/./b/../c")), Path::new("a/c"));
+		assert_eq!(normalize_path(Path::new("../../x")), Path::new("../../x"));
+		assert_eq!(normalize_path(Path::new("a/../../x")), Path::new("../x"));
+		assert_eq!(normalize_path(Path::new("/a/../../x")), Path::new("/x"));
+		assert_eq!(relative_import_path(Path::new("src/forms"), Path::new("../shared/Icon.vue")), "../../../shared/Icon.vue");
+	}
 
-pub const SUPPORTED_LANGUAGES: &[&str] = &[
-	"none", "javascript", "typescript", "python", "ruby",
-	"php", "java", "csharp", "go", "rust",
-	"swift", "kotlin", "dart", "elixir", "scala",
-	"lua", "perl"
-];
\ No newline at end of file
+	#[test]
+	fn test_line_diff() {
+		let diff = line_diff("a\nb\nc\n", "a\nx\nc\n");
+		assert_eq!(diff, [(' ', "a"), ('-', "b"), ('+', "x"), (' ', "c")]);
+	}
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_audit_and_import_review() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        "framework = [\"vue\"]\nstyle = [\"tailwind\"]\nlanguage = [\"typescript\"]\ncomponents_dir = \"components\"\n",
+    )
+    .unwrap();
+    let component_dir = temp_path.join("components").join("vue").join("tailwind").join("Article");
+    fs::create_dir_all(&component_dir).unwrap();
+    fs::write(
+        component_dir.join("Article.vue"),
+        "<template>\n  <article v-html=\"body\" />\n  <a :href=\"source\" target=\"_blank\">Source</a>\n</template>\n",
+    )
+    .unwrap();
+    fs::write(
+        component_dir.join("component.toml"),
+        "name = \"Article\"\nversion = \"0.1.0\"\nframework = \"vue\"\nstyle = \"tailwind\"\nlanguage = \"typescript\"\n\
+         description = \"\"\nauthor = \"test\"\ncreated_at = \"2025-01-01T00:00:00Z\"\nupdated_at = \"2025-01-01T00:00:00Z\"\ntags = []\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        duct::cmd(binary_path(), args)
+            .dir(temp_path)
+            .env("NO_COLOR", "1")
+            .stdin_null()
+            .stderr_capture()
+            .stdout_capture()
+            .unchecked()
+            .run()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&["audit"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "v-html is above the threshold: {}", stdout);
+    assert!(stdout.contains("high   components/vue/tailwind/Article/Article.vue:2"), "got: {}", stdout);
+    assert!(stdout.contains("[v-html]"), "got: {}", stdout);
+    assert!(stdout.contains("low    components/vue/tailwind/Article/Article.vue:3"), "got: {}", stdout);
+    assert!(stdout.contains("2 finding(s), 0 accepted"), "got: {}", stdout);
+
+    // Declining the review leaves the project untouched
+    fs::write(temp_path.join("answers.toml"), "[import]\naudit = false\n").unwrap();
+    let output = run(&["--answers", "answers.toml", "import", "Article", "--to", "src"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Security review of the imported components:"), "got: {}", stdout);
+    assert!(stdout.contains("Import cancelled."), "got: {}", stdout);
+    assert!(!temp_path.join("src").join("Article.vue").exists());
+
+    // Accepting needs a component, and lists the findings before asking
+    let output = run(&["audit", "--accept"]);
+    assert!(!output.status.success(), "--accept without a component should fail");
+    fs::write(temp_path.join("answers.toml"), "[audit]\naccept = false\n").unwrap();
+    let output = run(&["--answers", "answers.toml", "audit", "Article", "--accept"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("[v-html]") && stdout.contains("Nothing accepted"), "got: {}", stdout);
+    assert!(!fs::read_to_string(component_dir.join("component.toml")).unwrap().contains("accepted_risks"));
+
+    fs::write(temp_path.join("answers.toml"), "[audit]\naccept = true\n").unwrap();
+    let output = run(&["--answers", "answers.toml", "audit", "Article", "--accept", "--reason", "body is sanitized by the API"]);
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    let metadata = fs::read_to_string(component_dir.join("component.toml")).unwrap();
+    assert!(metadata.contains("rule = \"v-html\""), "got: {}", metadata);
+    assert!(metadata.contains("reason = \"body is sanitized by the API\""), "got: {}", metadata);
+
+    let output = run(&["audit"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "got: {}", stdout);
+    assert!(stdout.contains("No findings in 1 component(s) (2 accepted)"), "got: {}", stdout);
+
+    let output = run(&["import", "Article", "--to", "src"]);
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(temp_path.join("src").join("Article.vue").exists());
+
+    // Acceptance covers the reviewed lines, not every later use of the rule in the file
+    let source = fs::read_to_string(component_dir.join("Article.vue")).unwrap();
+    fs::write(component_dir.join("Article.vue"), source.replace("</template>", "  <p v-html=\"comment\" />\n</template>")).unwrap();
+    let output = run(&["audit"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "the new v-html is not accepted: {}", stdout);
+    assert!(stdout.contains("high   components/vue/tailwind/Article/Article.vue:4"), "got: {}", stdout);
+    assert!(stdout.contains("1 finding(s), 2 accepted"), "got: {}", stdout);
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
@@ -0,0 +1,33 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Writes a `vue/tailwind` component at version 2.0.0 into the library at
+/// `temp_path/components`, returning the path of its `component.toml`.
+pub fn write_component(temp_path: &Path, name: &str, dependencies: &str) -> PathBuf {
+    let component_dir = temp_path.join("components").join("vue").join("tailwind").join(name);
+    fs::create_dir_all(&component_dir).unwrap();
+    fs::write(component_dir.join(format!("{}.vue", name)), "<template><div /></template>\n").unwrap();
+    fs::write(
+        component_dir.join("component.toml"),
+        format!(
+            r#"
+            name = "{}"
+            version = "2.0.0"
+            framework = "vue"
+            style = "tailwind"
+            language = "typescript"
+            description = ""
+            author = "test"
+            created_at = "2025-01-01T00:00:00Z"
+            updated_at = "2025-01-01T00:00:00Z"
+            tags = []
+
+            [dependencies]
+            dependencies = [{}]
+            "#,
+            name, dependencies
+        ),
+    )
+    .unwrap();
+    component_dir.join("component.toml")
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+mod common;
+use common::write_component;
+
+#[test]
+fn test_dependents_in_library_and_projects() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        r#"
+        framework = ["vue"]
+        style = ["tailwind"]
+        language = ["typescript"]
+        components_dir = "components"
+        projects = ["apps/admin", "apps/site"]
+        "#,
+    )
+    .unwrap();
+    write_component(temp_path, "Icon", "");
+    write_component(temp_path, "Button", r#"{ Internal = "Icon@^1.0.0" }"#);
+    write_component(temp_path, "Dialog", r#"{ Internal = "Button" }"#);
+
+    let admin = temp_path.join("apps").join("admin");
+    fs::create_dir_all(&admin).unwrap();
+    fs::write(
+        admin.join(".component-manager.lock"),
+        "[[component]]\nname = \"AppButton\"\nsource = \"vue/tailwind/Button\"\nversion = \"2.0.0\"\nfiles = [\"src/components/AppButton.vue\"]\n",
+    )
+    .unwrap();
+    let site = temp_path.join("apps").join("site").join("src");
+    fs::create_dir_all(site.join("node_modules")).unwrap();
+    fs::write(site.join("Icon.vue"), "").unwrap();
+    fs::write(site.join("node_modules").join("Dialog.vue"), "").unwrap();
+
+    let output = duct::cmd!(binary_path(), "dependents", "Icon", "--projects")
+        .dir(temp_path)
+        .env("NO_COLOR", "1")
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "dependents failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("vue/tailwind/Button  requires ^1.0.0  ⚠ excludes the current version"), "Expected the direct dependent, got: {}", stdout);
+    assert!(stdout.contains("vue/tailwind/Dialog  via vue/tailwind/Button"), "Expected the transitive dependent, got: {}", stdout);
+    assert!(stdout.contains("AppButton 2.0.0 (from vue/tailwind/Button)"), "Expected the renamed copy, got: {}", stdout);
+    assert!(stdout.contains("src/Icon.vue"), "Expected the copy found by file name, got: {}", stdout);
+    assert!(!stdout.contains("Dialog.vue"), "node_modules should be skipped, got: {}", stdout);
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
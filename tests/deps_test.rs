@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+mod common;
+use common::write_component;
+
+fn publish(mirror: &Path, name: &str, latest: &str) {
+    let package_dir = mirror.join(name);
+    fs::create_dir_all(&package_dir).unwrap();
+    fs::write(
+        package_dir.join("package.json"),
+        format!(r#"{{ "name": "{}", "dist-tags": {{ "latest": "{}" }} }}"#, name, latest),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_deps_outdated_bumps_ranges() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        "framework = [\"vue\"]\nstyle = [\"tailwind\"]\nlanguage = [\"typescript\"]\ncomponents_dir = \"components\"\nregistry = \"file://mirror\"\n",
+    )
+    .unwrap();
+    publish(&temp_path.join("mirror"), "vue", "3.4.21");
+    publish(&temp_path.join("mirror"), "@floating-ui/vue", "1.1.0");
+    let button = write_component(temp_path, "Button", r#"{ Npm = "vue@^2.6.0" }"#);
+    let popover = write_component(
+        temp_path,
+        "Popover",
+        r#"{ Npm = "vue@^3.0.0" }, { Package = { name = "@floating-ui/vue", version = "^0.2.0", kind = "peer" } }, { Npm = "unpublished@^1.0.0" }"#,
+    );
+
+    let run = |args: &[&str]| {
+        let output = duct::cmd(binary_path(), args)
+            .dir(temp_path)
+            .env("NO_COLOR", "1")
+            .stdin_null()
+            .stderr_capture()
+            .stdout_capture()
+            .unchecked()
+            .run()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "deps outdated failed: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    // Declining leaves the components alone
+    fs::write(temp_path.join("answers.toml"), "[deps]\nbump = false\n").unwrap();
+    let stdout = run(&["--answers", "answers.toml", "deps", "outdated"]);
+    assert!(stdout.contains("vue/tailwind/Button   vue               ^2.6.0  3.4.21"), "Expected the outdated range, got: {}", stdout);
+    assert!(stdout.contains("vue/tailwind/Popover  @floating-ui/vue  ^0.2.0  1.1.0"), "Expected the outdated peer, got: {}", stdout);
+    assert!(!stdout.contains("vue/tailwind/Popover  vue "), "^3.0.0 accepts the latest major: {}", stdout);
+    assert!(stdout.contains("unpublished is not in the mirror"), "Expected the unknown package to be reported, got: {}", stdout);
+    assert!(stdout.contains("Ranges left unchanged"));
+
+    let stdout = run(&["deps", "outdated", "--yes"]);
+    assert!(stdout.contains("vue/tailwind/Button: vue ^2.6.0 → ^3.4.21"), "Expected the bump, got: {}", stdout);
+    assert!(fs::read_to_string(&button).unwrap().contains("name = \"vue\"\nversion = \"^3.4.21\""));
+    let popover = fs::read_to_string(&popover).unwrap();
+    assert!(popover.contains("version = \"^1.1.0\""), "Expected the bumped peer, got: {}", popover);
+    assert!(popover.contains("kind = \"peer\""), "The kind should be kept, got: {}", popover);
+
+    let stdout = run(&["deps", "outdated", "--registry", &temp_path.join("mirror").display().to_string()]);
+    assert!(stdout.contains("Every component accepts the latest major"), "Expected nothing left, got: {}", stdout);
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
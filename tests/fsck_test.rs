@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_fsck_reports_and_repairs() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        "framework = [\"vue\"]\nstyle = [\"tailwind\"]\nlanguage = [\"typescript\"]\ncomponents_dir = \"components\"\n",
+    )
+    .unwrap();
+    let style_dir = temp_path.join("components").join("vue").join("tailwind");
+    fs::create_dir_all(style_dir.join("Modal")).unwrap();
+    fs::write(style_dir.join("Modal").join("Modal.vue"), "<script setup lang=\"ts\">\nimport { ref } from 'vue'\n</script>\n").unwrap();
+    fs::create_dir_all(style_dir.join("Tooltip")).unwrap();
+    fs::write(style_dir.join("Tooltip").join("Tooltip.vue"), "<template />\n").unwrap();
+    fs::write(
+        style_dir.join("Tooltip").join("component.toml"),
+        "name = \"Tooltip\"\nversion = \"1.0.0\"\nframework = \"react\"\nstyle = \"tailwind\"\nlanguage = \"typescript\"\n\
+         description = \"\"\nauthor = \"\"\ncreated_at = \"\"\nupdated_at = \"\"\ntags = []\n",
+    )
+    .unwrap();
+    fs::create_dir_all(style_dir.join("Unused")).unwrap();
+
+    let run = |args: &[&str]| {
+        duct::cmd(binary_path(), args)
+            .dir(temp_path)
+            .env("NO_COLOR", "1")
+            .stdin_null()
+            .stderr_capture()
+            .stdout_capture()
+            .unchecked()
+            .run()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&["fsck"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success());
+    assert!(stdout.contains("components/vue/tailwind/Unused: empty directory"), "got: {}", stdout);
+    assert!(stdout.contains("components/vue/tailwind/Modal: component folder without component.toml"), "got: {}", stdout);
+    assert!(
+        stdout.contains("components/vue/tailwind/Tooltip: component.toml has framework = \"react\" but the folder says \"vue\""),
+        "got: {}",
+        stdout
+    );
+    assert!(stdout.contains("3 problem(s) found"), "got: {}", stdout);
+
+    // Tooltip really is a React component, so it moves to react/tailwind
+    fs::write(
+        temp_path.join("answers.toml"),
+        "[fsck]\nremove_empty = true\ngenerate = [\"components/vue/tailwind/Modal\"]\nupdate_metadata = []\nrelocate = [\"components/vue/tailwind/Tooltip\"]\n",
+    )
+    .unwrap();
+    let output = run(&["--answers", "answers.toml", "fsck", "--repair"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "repair failed: {}{}", stdout, String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Repaired 3 problem(s)"), "got: {}", stdout);
+    assert!(!style_dir.join("Unused").exists());
+    let metadata = fs::read_to_string(style_dir.join("Modal").join("component.toml")).unwrap();
+    assert!(metadata.contains("name = \"Modal\""), "got: {}", metadata);
+    assert!(metadata.contains("language = \"typescript\""), "got: {}", metadata);
+    assert!(temp_path.join("components/react/tailwind/Tooltip/Tooltip.vue").exists());
+
+    let output = run(&["fsck"]);
+    assert!(output.status.success(), "got: {}", String::from_utf8_lossy(&output.stdout));
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+mod common;
+use common::write_component;
+
+#[test]
+fn test_graph_formats() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        "framework = [\"vue\"]\nstyle = [\"tailwind\"]\nlanguage = [\"typescript\"]\ncomponents_dir = \"components\"\n",
+    )
+    .unwrap();
+    write_component(temp_path, "Dialog", r#"{ Internal = "Button" }, { Npm = "vue@^3.0.0" }"#);
+    write_component(temp_path, "Button", r#"{ Internal = "Icon" }"#);
+    write_component(temp_path, "Icon", "");
+
+    let output = duct::cmd!(binary_path(), "graph", "Dialog", "--format", "dot", "--packages")
+        .dir(temp_path)
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "graph failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.starts_with("digraph components {"), "Expected DOT, got: {}", stdout);
+    assert!(stdout.contains("\"vue/tailwind/Button\" -> \"vue/tailwind/Icon\";"), "Expected transitive edges, got: {}", stdout);
+    assert!(stdout.contains("\"vue/tailwind/Dialog\" -> \"vue@^3.0.0\";"), "Expected package leaves, got: {}", stdout);
+
+    // A cycle fails the command but still prints the graph
+    write_component(temp_path, "Icon", r#"{ Internal = "Dialog" }"#);
+    let output = duct::cmd!(binary_path(), "graph", "--format", "mermaid")
+        .dir(temp_path)
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "A cycle should fail the command");
+    assert!(stdout.starts_with("graph LR\n"), "Expected Mermaid, got: {}", stdout);
+    assert!(stdout.contains("linkStyle 0,1,2 stroke:#d00"), "Expected highlighted cycle edges, got: {}", stdout);
+    assert!(
+        stderr.contains("Dependency cycle: vue/tailwind/Button → vue/tailwind/Icon → vue/tailwind/Dialog → vue/tailwind/Button"),
+        "Expected the cycle to be reported, got: {}",
+        stderr
+    );
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
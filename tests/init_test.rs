@@ -0,0 +1,245 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_init_auto_detects_stack() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join("package.json"),
+        r#"{ "dependencies": { "react": "^18.2.0", "next": "14.1.0" }, "devDependencies": { "typescript": "^5.3.0", "tailwindcss": "^3.4.0" } }"#,
+    )
+    .unwrap();
+
+    let output = duct::cmd!(binary_path(), "init", "--auto")
+        .dir(temp_path)
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "init --auto failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("framework: nextjs (package.json depends on next 14.1.0)"), "got: {}", stdout);
+    assert!(stdout.contains("style: tailwind (package.json depends on tailwindcss ^3.4.0)"), "got: {}", stdout);
+
+    let config: toml::Table = fs::read_to_string(temp_path.join(".component-manager.toml")).unwrap().parse().unwrap();
+    let values = |key: &str| -> Vec<String> {
+        config[key].as_array().unwrap().iter().map(|v| v.as_str().unwrap().to_string()).collect()
+    };
+    assert_eq!(values("framework"), ["nextjs", "react"]);
+    assert_eq!(values("style"), ["tailwind"]);
+    assert_eq!(values("language"), ["typescript"]);
+}
+
+#[test]
+fn test_init_auto_without_manifests() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+
+    let output = duct::cmd!(binary_path(), "init", "--auto")
+        .dir(temp_path)
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "init --auto failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Could not detect the project stack"));
+    let config = fs::read_to_string(temp_path.join(".component-manager.toml")).unwrap();
+    assert!(config.contains("framework = [\"none\"]"), "got: {}", config);
+}
+
+#[test]
+fn test_init_auto_keeps_undetected_values() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let config_path = temp_path.join(".component-manager.toml");
+    fs::write(&config_path, "framework = [\"vue\"]\nstyle = [\"tailwind\"]\nlanguage = [\"javascript\"]\n").unwrap();
+    fs::write(temp_path.join("package.json"), r#"{ "devDependencies": { "typescript": "^5.3.0" } }"#).unwrap();
+
+    let run = |args: &[&str]| {
+        duct::cmd(binary_path(), args)
+            .dir(temp_path)
+            .stdin_null()
+            .stderr_capture()
+            .stdout_capture()
+            .unchecked()
+            .run()
+            .expect("Failed to execute command")
+    };
+
+    // Changes are still confirmed before writing
+    let output = run(&["init", "--auto"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --yes"), "got: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(fs::read_to_string(&config_path).unwrap().contains("language = [\"javascript\"]"));
+
+    let output = run(&["init", "--auto", "--yes"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "init --auto failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("+ language = [\"typescript\"]"), "got: {}", stdout);
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "framework = [\"vue\"]\nstyle = [\"tailwind\"]\nlanguage = [\"typescript\"]\n"
+    );
+
+    fs::remove_file(temp_path.join("package.json")).unwrap();
+    let output = run(&["init", "--auto"]);
+    assert!(output.status.success(), "init --auto failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("already up to date"));
+}
+
+#[test]
+fn test_init_prompts_list_names() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join("answers.toml"),
+        "[init]\nframework = [\"Next.js\", \"React\"]\nstyle = [\"Tailwind CSS\"]\nlanguage = [\"TypeScript\"]\nreadme = false\n",
+    )
+    .unwrap();
+
+    let output = duct::cmd!(binary_path(), "--answers", "answers.toml", "init")
+        .dir(temp_path)
+        .env("COMPONENT_MANAGER_CONFIG", temp_path.join("missing.toml"))
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "init failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    // The config keeps the ids of the entries picked by name
+    let config = fs::read_to_string(temp_path.join(".component-manager.toml")).unwrap();
+    assert!(config.contains("framework = [\"react\", \"nextjs\"]"), "got: {}", config);
+    assert!(config.contains("style = [\"tailwind\"]"), "got: {}", config);
+    assert!(config.contains("language = [\"typescript\"]"), "got: {}", config);
+}
+
+#[test]
+fn test_init_preset_creates_config() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+
+    let output = duct::cmd!(binary_path(), "init", "--preset", "nuxt-tailwind-ts", "--yes", "--readme")
+        .dir(temp_path)
+        .env("COMPONENT_MANAGER_CONFIG", temp_path.join("missing.toml"))
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "init --preset failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    let config = fs::read_to_string(temp_path.join(".component-manager.toml")).unwrap();
+    assert!(config.contains("framework = [\"nuxt\", \"vue\"]"), "got: {}", config);
+    assert!(config.contains("style = [\"tailwind\"]"), "got: {}", config);
+    assert!(config.contains("language = [\"typescript\"]"), "got: {}", config);
+    assert!(temp_path.join("components").is_dir());
+    assert!(temp_path.join("components/README.md").exists());
+
+    let output = duct::cmd!(binary_path(), "init", "--preset", "nope", "--yes")
+        .dir(temp_path)
+        .env("COMPONENT_MANAGER_CONFIG", temp_path.join("missing.toml"))
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Available presets: angular-scss-ts"));
+}
+
+#[test]
+fn test_init_merges_existing_config() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let global = temp_path.join("global.toml");
+    fs::write(
+        &global,
+        r#"
+[presets.team]
+description = "Team defaults"
+framework = ["react"]
+style = ["scss"]
+language = ["typescript"]
+components_dir = "ui"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        r#"# Shared with the design team
+framework = ["vue"]
+style = ["tailwind"]
+language = ["javascript"]
+registry = "https://npm.example.com"
+
+[lint]
+max_lines = 120
+"#,
+    )
+    .unwrap();
+
+    let output = duct::cmd!(binary_path(), "init", "--preset", "team", "--yes")
+        .dir(temp_path)
+        .env("COMPONENT_MANAGER_CONFIG", &global)
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "init --preset failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Using preset team: Team defaults"), "got: {}", stdout);
+    assert!(stdout.contains("- framework = [\"vue\"]\n"), "got: {}", stdout);
+    assert!(stdout.contains("+ framework = [\"react\"]\n"), "got: {}", stdout);
+    assert!(!stdout.contains("registry"), "got: {}", stdout);
+    assert!(stdout.contains("+ components_dir = \"ui\""), "got: {}", stdout);
+
+    let config = fs::read_to_string(temp_path.join(".component-manager.toml")).unwrap();
+    assert_eq!(
+        config,
+        r#"# Shared with the design team
+framework = ["react"]
+style = ["scss"]
+language = ["typescript"]
+registry = "https://npm.example.com"
+components_dir = "ui"
+
+[lint]
+max_lines = 120
+"#
+    );
+    assert!(temp_path.join("ui").is_dir());
+    assert!(!temp_path.join("ui/README.md").exists());
+
+    let output = duct::cmd!(binary_path(), "init", "--preset", "team", "--yes")
+        .dir(temp_path)
+        .env("COMPONENT_MANAGER_CONFIG", &global)
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("already up to date"));
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
@@ -0,0 +1,379 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+/// Creates a project whose components need `vue` and `@vueuse/core`, and a
+/// fake `npm` that logs its arguments and fails for the package named in
+/// `FAIL_ON`.
+fn setup_project(temp_path: &Path) -> PathBuf {
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        r#"
+        framework = ["vue"]
+        style = ["none"]
+        language = ["javascript"]
+        components_dir = "components"
+        package_manager = "npm"
+        "#,
+    )
+    .unwrap();
+    fs::write(temp_path.join("package.json"), r#"{ "devDependencies": { "@types/node": "^18.0.0" } }"#).unwrap();
+
+    let component_dir = temp_path.join("components").join("vue").join("none").join("Modal");
+    fs::create_dir_all(&component_dir).unwrap();
+    fs::write(
+        component_dir.join("component.toml"),
+        r#"
+        name = "Modal"
+        version = "0.1.0"
+        framework = "vue"
+        style = "none"
+        language = "javascript"
+        description = ""
+        author = "test"
+        created_at = "2025-01-01T00:00:00Z"
+        updated_at = "2025-01-01T00:00:00Z"
+        tags = []
+
+        [dependencies]
+        dependencies = [{ Npm = "vue@^3.0.0" }, { Npm = "@types/node@^20.0.0" }]
+        "#,
+    )
+    .unwrap();
+
+    let bin_dir = temp_path.join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    let npm = bin_dir.join("npm");
+    fs::write(
+        &npm,
+        "#!/bin/sh\necho \"$@\" >> npm.log\nfor arg in \"$@\"; do\n  case \"$arg\" in \"$FAIL_ON\"*) echo \"cannot install $arg\" >&2; exit 3;; esac\ndone\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&npm, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let path = std::env::var("PATH").unwrap_or_default();
+    std::env::join_paths(std::iter::once(bin_dir).chain(std::env::split_paths(&path)))
+        .unwrap()
+        .into()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_install_runs_commands() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let path = setup_project(temp_path);
+
+    // --dry-run only prints
+    let output = duct::cmd!(binary_path(), "install", "--dry-run")
+        .dir(temp_path)
+        .env("PATH", &path)
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "Dry run failed: {}", stdout);
+    assert!(stdout.contains("npm install --save vue@^3.0.0"), "Expected the plan, got: {}", stdout);
+    assert!(!temp_path.join("npm.log").exists(), "A dry run must not run anything");
+
+    let output = duct::cmd!(binary_path(), "install", "--yes")
+        .dir(temp_path)
+        .env("PATH", &path)
+        .env("FAIL_ON", "none")
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "Install failed: {}{}", stdout, stderr);
+    assert!(stdout.contains("Summary"), "Expected a summary, got: {}", stdout);
+    assert_eq!(
+        fs::read_to_string(temp_path.join("npm.log")).unwrap(),
+        "install --save vue@^3.0.0\ninstall --save-dev @types/node@^20.0.0\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_install_stops_on_failure() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let path = setup_project(temp_path);
+
+    let output = duct::cmd!(binary_path(), "install", "--yes")
+        .dir(temp_path)
+        .env("PATH", &path)
+        .env("FAIL_ON", "vue")
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "Install should fail: {}", stdout);
+    assert!(stdout.contains("(exit 3)"), "Expected the exit code in the summary, got: {}", stdout);
+    assert!(stdout.contains("(skipped)"), "The dev install should be skipped, got: {}", stdout);
+    assert!(stderr.contains("cannot install vue@^3.0.0"), "Command output should be streamed, got: {}", stderr);
+    assert_eq!(fs::read_to_string(temp_path.join("npm.log")).unwrap(), "install --save vue@^3.0.0\n");
+
+    // Declining the confirmation runs nothing
+    fs::write(temp_path.join("answers.toml"), "[install]\nconfirm = false\n").unwrap();
+    fs::remove_file(temp_path.join("npm.log")).unwrap();
+    let output = duct::cmd!(binary_path(), "--answers", "answers.toml", "install")
+        .dir(temp_path)
+        .env("PATH", &path)
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "Declining should not fail: {}", stdout);
+    assert!(stdout.contains("Installation cancelled"), "Expected a cancellation, got: {}", stdout);
+    assert!(!temp_path.join("npm.log").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_install_dependency_kinds() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let path = setup_project(temp_path);
+    fs::write(
+        temp_path.join("components").join("vue").join("none").join("Modal").join("component.toml"),
+        r#"
+        name = "Modal"
+        version = "0.1.0"
+        framework = "vue"
+        style = "none"
+        language = "javascript"
+        description = ""
+        author = "test"
+        created_at = "2025-01-01T00:00:00Z"
+        updated_at = "2025-01-01T00:00:00Z"
+        tags = []
+
+        [dependencies]
+        dependencies = [
+            { Package = { name = "vue", version = "^3.3.0", kind = "peer" } },
+            { Package = { name = "@testing-library/vue", version = "^8.0.0", kind = "dev" } },
+            { Package = { name = "@vueuse/core", version = "^10.0.0", kind = "optional", reason = "for the auto-resize feature" } },
+            { Package = { name = "focus-trap", version = "^7.0.0", kind = "optional" } },
+        ]
+        "#,
+    )
+    .unwrap();
+    fs::write(
+        temp_path.join("answers.toml"),
+        "[install]\noptional = [\"@vueuse/core@^10.0.0\"]\nconfirm = true\n",
+    )
+    .unwrap();
+
+    let output = duct::cmd!(binary_path(), "--answers", "answers.toml", "install")
+        .dir(temp_path)
+        .env("PATH", &path)
+        .env("FAIL_ON", "none")
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "Install failed: {}{}", stdout, stderr);
+    assert!(stdout.contains("vue@^3.3.0 is a peer dependency"), "Peers should be reported: {}", stdout);
+    assert!(stdout.contains("@vueuse/core@^10.0.0 (for the auto-resize feature)"), "Reasons should be shown: {}", stdout);
+    assert_eq!(
+        fs::read_to_string(temp_path.join("npm.log")).unwrap(),
+        "install --save @vueuse/core@^10.0.0\ninstall --save-dev @testing-library/vue@^8.0.0\n"
+    );
+}
+
+#[test]
+fn test_install_other_ecosystems() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let path = setup_project(temp_path);
+    fs::write(
+        temp_path.join("components").join("vue").join("none").join("Modal").join("component.toml"),
+        r#"
+        name = "Modal"
+        version = "0.1.0"
+        framework = "django"
+        style = "none"
+        language = "python"
+        description = ""
+        author = "test"
+        created_at = "2025-01-01T00:00:00Z"
+        updated_at = "2025-01-01T00:00:00Z"
+        tags = []
+
+        [dependencies]
+        dependencies = [{ Pip = "django@>=4.2,<5" }, { Pip = "django-htmx@*" }, { Go = "github.com/a-h/templ@v0.2.543" }]
+        "#,
+    )
+    .unwrap();
+    fs::write(temp_path.join("requirements.txt"), "Django==4.2.7
+").unwrap();
+
+    let output = duct::cmd!(binary_path(), "install", "--dry-run")
+        .dir(temp_path)
+        .env("PATH", &path)
+        .stdin_null()
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "Dry run failed: {}", stdout);
+    assert!(stdout.contains("pip install django-htmx\n"), "Only the missing package should be installed: {}", stdout);
+    assert!(stdout.contains("go get github.com/a-h/templ@v0.2.543"), "Expected a go command: {}", stdout);
+    assert!(!stdout.contains("npm install"), "Nothing comes from npm: {}", stdout);
+}
+
+#[test]
+fn test_install_resolves_library_layout() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let path = setup_project(temp_path);
+    let write_component = |framework: &str, name: &str, dependencies: &str| {
+        let component_dir = temp_path.join("components").join(framework).join("none").join(name);
+        fs::create_dir_all(&component_dir).unwrap();
+        fs::write(
+            component_dir.join("component.toml"),
+            format!(
+                "name = \"{name}\"\nversion = \"0.1.0\"\nframework = \"{framework}\"\nstyle = \"none\"\nlanguage = \"javascript\"\n\
+                 description = \"\"\nauthor = \"test\"\ncreated_at = \"\"\nupdated_at = \"\"\ntags = []\n\n\
+                 [dependencies]\ndependencies = [{dependencies}]\n"
+            ),
+        )
+        .unwrap();
+    };
+    write_component("vue", "Dialog", r#"{ Internal = "Button" }, { Npm = "focus-trap@^7.0.0" }"#);
+    write_component("vue", "Button", r#"{ Npm = "clsx@^2.0.0" }"#);
+    write_component("react", "Button", r#"{ Npm = "react@^18.0.0" }"#);
+
+    let install = |args: &[&str]| {
+        let output = duct::cmd(binary_path(), args)
+            .dir(temp_path)
+            .env("PATH", &path)
+            .stdin_null()
+            .stderr_capture()
+            .stdout_capture()
+            .unchecked()
+            .run()
+            .expect("Failed to execute command");
+        (output.status.success(), String::from_utf8_lossy(&output.stdout).to_string(), String::from_utf8_lossy(&output.stderr).to_string())
+    };
+
+    // The components a component depends on are installed along with it
+    let (success, stdout, stderr) = install(&["install", "Dialog", "--dry-run"]);
+    assert!(success, "install failed: {}{}", stdout, stderr);
+    assert!(stdout.contains("npm install --save clsx@^2.0.0 focus-trap@^7.0.0\n"), "Expected the whole tree, got: {}", stdout);
+
+    let (success, _, stderr) = install(&["install", "Button", "--dry-run"]);
+    assert!(!success, "An ambiguous name should fail");
+    assert!(stderr.contains("use one of: react/none/Button, vue/none/Button"), "Expected the candidates, got: {}", stderr);
+
+    let (success, stdout, stderr) = install(&["install", "react/none/Button", "--dry-run"]);
+    assert!(success, "install failed: {}{}", stdout, stderr);
+    assert!(stdout.contains("npm install --save react@^18.0.0\n"), "Expected the react button, got: {}", stdout);
+
+    // Without a name, every component in the tree is aggregated
+    let (success, stdout, stderr) = install(&["install", "--dry-run"]);
+    assert!(success, "install failed: {}{}", stdout, stderr);
+    assert!(
+        stdout.contains("npm install --save clsx@^2.0.0 focus-trap@^7.0.0 react@^18.0.0 vue@^3.0.0\n"),
+        "Expected every component, got: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_install_writes_manifest() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    let path = setup_project(temp_path);
+    let manifest = "{\n  \"name\": \"app\",\n  \"dependencies\": {\n    \"vue\": \"^3.2.0\"\n  },\n  \"devDependencies\": {\n    \"@types/node\": \"^18.0.0\"\n  }\n}\n";
+    fs::write(temp_path.join("package.json"), manifest).unwrap();
+    fs::write(
+        temp_path.join("components").join("vue").join("none").join("Modal").join("component.toml"),
+        r#"
+        name = "Modal"
+        version = "0.1.0"
+        framework = "vue"
+        style = "none"
+        language = "javascript"
+        description = ""
+        author = "test"
+        created_at = "2025-01-01T00:00:00Z"
+        updated_at = "2025-01-01T00:00:00Z"
+        tags = []
+
+        [dependencies]
+        dependencies = [
+            { Npm = "vue@^3.3.0" },
+            { Npm = "@types/node@^20.0.0" },
+            { Npm = "focus-trap@^7.0.0" },
+            { Package = { name = "pinia", version = "^2.0.0", kind = "peer" } },
+        ]
+        "#,
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let output = duct::cmd(binary_path(), args)
+            .dir(temp_path)
+            .env("PATH", &path)
+            .stdin_null()
+            .stderr_capture()
+            .stdout_capture()
+            .unchecked()
+            .run()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "install failed: {}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let stdout = run(&["install", "--write-manifest", "--dry-run"]);
+    assert!(stdout.contains("+ dependencies.focus-trap: ^7.0.0"), "Expected the additions, got: {}", stdout);
+    assert_eq!(fs::read_to_string(temp_path.join("package.json")).unwrap(), manifest, "A dry run must not write");
+
+    let stdout = run(&["install", "--write-manifest", "--yes"]);
+    assert!(stdout.contains("~ dependencies.vue: ^3.2.0 → ^3.3.0"), "Expected the changes, got: {}", stdout);
+    assert!(stdout.contains("~ devDependencies.@types/node: ^18.0.0 → ^20.0.0"), "Expected the changes, got: {}", stdout);
+    assert!(stdout.contains("+ peerDependencies.pinia: ^2.0.0"), "Expected the peer, got: {}", stdout);
+    assert_eq!(
+        fs::read_to_string(temp_path.join("package.json")).unwrap(),
+        "{\n  \"name\": \"app\",\n  \"dependencies\": {\n    \"focus-trap\": \"^7.0.0\",\n    \"vue\": \"^3.3.0\"\n  },\n  \"devDependencies\": {\n    \"@types/node\": \"^20.0.0\"\n  },\n  \"peerDependencies\": {\n    \"pinia\": \"^2.0.0\"\n  }\n}\n"
+    );
+    assert!(!temp_path.join("npm.log").exists(), "The package manager must not run");
+
+    let stdout = run(&["install", "--write-manifest", "--yes"]);
+    assert!(stdout.contains("package.json already declares every dependency"), "Expected no changes, got: {}", stdout);
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_lint_reports_and_fixes() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        r#"framework = ["vue"]
+style = ["tailwind"]
+language = ["typescript"]
+components_dir = "components"
+
+[lint]
+max_lines = 50
+
+[[lint.custom]]
+name = "no-inline-style"
+pattern = "style=\"[^\"]*\""
+message = "Use classes instead of inline styles"
+files = ["*.vue"]
+"#,
+    )
+    .unwrap();
+    let component_dir = temp_path.join("components").join("vue").join("tailwind").join("Card");
+    fs::create_dir_all(&component_dir).unwrap();
+    fs::write(
+        component_dir.join("Card.vue"),
+        "<template>\n  <div style=\"padding: 4px\"><slot /></div>\n</template>\n<script setup>\nconsole.log('card')\n</script>\n",
+    )
+    .unwrap();
+    fs::write(
+        component_dir.join("component.toml"),
+        "version = \"0.1.0\"\nframework = \"vue\"\nstyle = \"tailwind\"\nlanguage = \"typescript\"\ndescription = \"A card\"\n\
+         author = \"test\"\ncreated_at = \"2025-01-01T00:00:00Z\"\nupdated_at = \"2025-01-01T00:00:00Z\"\ntags = []\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        duct::cmd(binary_path(), args)
+            .dir(temp_path)
+            .env("NO_COLOR", "1")
+            .stdin_null()
+            .stderr_capture()
+            .stdout_capture()
+            .unchecked()
+            .run()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&["lint", "Card"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "The missing name is an error: {}", stdout);
+    assert!(stdout.contains("components/vue/tailwind/Card/Card.vue:2: warning Use classes instead of inline styles [no-inline-style]"), "got: {}", stdout);
+    assert!(stdout.contains("components/vue/tailwind/Card/Card.vue:5: warning unexpected console.log [no-console]"), "got: {}", stdout);
+    assert!(stdout.contains("component.toml:1: error missing required field `name` [required-fields]"), "got: {}", stdout);
+    assert!(stdout.contains("1 error(s), 2 warning(s)"), "got: {}", stdout);
+    assert!(stdout.contains("2 problem(s) can be fixed with --fix"), "got: {}", stdout);
+
+    let output = run(&["lint", "--fix"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "Only a warning should remain: {}", stdout);
+    assert!(stdout.contains("Fixed 2 problem(s)"), "got: {}", stdout);
+    assert!(stdout.contains("0 error(s), 1 warning(s)"), "got: {}", stdout);
+    assert!(!fs::read_to_string(component_dir.join("Card.vue")).unwrap().contains("console.log"));
+    assert!(fs::read_to_string(component_dir.join("component.toml")).unwrap().contains("name = \"Card\""));
+}
+
+fn binary_path() -> PathBuf {
+    std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager")
+}
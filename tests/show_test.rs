@@ -54,7 +54,7 @@ fn test_show_components() {
     
     // Change to the temp directory
     let original_dir = std::env::current_dir().expect("Failed to get current directory");
-    std::env::set_current_dir(&temp_path).expect("Failed to change to temp directory");
+    std::env::set_current_dir(temp_path).expect("Failed to change to temp directory");
     
     // Get the path to the binary
     let binary_path = std::env::current_exe()
@@ -137,7 +137,7 @@ fn test_show_components_empty() {
     
     // Change to the temp directory
     let original_dir = std::env::current_dir().expect("Failed to get current directory");
-    std::env::set_current_dir(&temp_path).expect("Failed to change to temp directory");
+    std::env::set_current_dir(temp_path).expect("Failed to change to temp directory");
     
     // The show_components function should handle the case when no components exist
     // Note: In a real test, we would capture stdout and verify the output
@@ -146,3 +146,56 @@ fn test_show_components_empty() {
     std::env::set_current_dir(original_dir).expect("Failed to change back to original directory");
     temp_dir.close().expect("Failed to clean up temp directory");
 }
+
+#[test]
+fn test_show_all_uses_taxonomy_order() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join(".component-manager.toml"),
+        r#"
+framework = ["react"]
+style = ["tailwind", "css-modules"]
+language = ["typescript"]
+components_dir = "components"
+
+[taxonomy.styles.css-modules]
+name = "CSS modules"
+"#,
+    )
+    .unwrap();
+    for (style, name) in [("css-modules", "Card"), ("tailwind", "Button")] {
+        let component_dir = temp_path.join("components").join("react").join(style).join(name);
+        fs::create_dir_all(&component_dir).unwrap();
+        fs::write(
+            component_dir.join("component.toml"),
+            format!(
+                "name = \"{name}\"\nversion = \"0.1.0\"\nframework = \"react\"\nstyle = \"{style}\"\nlanguage = \"typescript\"\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    let binary_path = std::env::current_exe()
+        .expect("Failed to get current executable")
+        .parent()
+        .expect("Failed to get parent directory")
+        .parent()
+        .expect("Failed to get parent directory")
+        .join("component-manager");
+    let output = duct::cmd!(binary_path, "show", "--all")
+        .dir(temp_path)
+        .env("COMPONENT_MANAGER_CONFIG", temp_path.join("missing.toml"))
+        .stderr_capture()
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "show --all failed: {}", stdout);
+    // Built-in styles come first, then the ones the config adds
+    let tailwind = stdout.find("  - Tailwind CSS [tailwind] (1):").unwrap_or_else(|| panic!("got: {}", stdout));
+    let modules = stdout.find("  - CSS modules [css-modules] (1):").unwrap_or_else(|| panic!("got: {}", stdout));
+    assert!(tailwind < modules, "got: {}", stdout);
+    assert!(stdout.contains("\nreact:\n"), "got: {}", stdout);
+}